@@ -0,0 +1,91 @@
+#![warn(missing_docs)]
+
+//! The derive macro for the [chromium](https://docs.rs/chromium) crate.
+//!
+//! Don't depend on this crate directly, enable the `derive` feature of
+//! `chromium` instead.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+  parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields,
+  GenericParam,
+};
+
+/// Derives `StableLayout` for a `repr(C)` or `repr(transparent)` struct.
+///
+/// Every field must also be `StableLayout`, which is checked at compile time.
+/// Every type parameter of the struct gets a `StableLayout` bound.
+#[proc_macro_derive(StableLayout)]
+pub fn derive_stable_layout(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  expand(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn expand(mut input: DeriveInput) -> Result<TokenStream2, Error> {
+  let fields = match &input.data {
+    Data::Struct(s) => &s.fields,
+    _ => {
+      return Err(Error::new(
+        Span::call_site(),
+        "`StableLayout` can only be derived for structs",
+      ))
+    }
+  };
+  check_repr(&input)?;
+
+  let field_types: Vec<_> = match fields {
+    Fields::Named(f) => f.named.iter().map(|f| &f.ty).collect(),
+    Fields::Unnamed(f) => f.unnamed.iter().map(|f| &f.ty).collect(),
+    Fields::Unit => Vec::new(),
+  };
+
+  for param in input.generics.params.iter_mut() {
+    if let GenericParam::Type(t) = param {
+      t.bounds.push(parse_quote!(::chromium::StableLayout));
+    }
+  }
+  let name = &input.ident;
+  let (impl_generics, ty_generics, where_clause) =
+    input.generics.split_for_impl();
+
+  Ok(quote! {
+    const _: () = {
+      fn assert_stable_layout<T: ::chromium::StableLayout + ?Sized>() {}
+      #[allow(dead_code)]
+      fn assert_fields #impl_generics (_: &#name #ty_generics) #where_clause {
+        #( assert_stable_layout::<#field_types>(); )*
+      }
+    };
+    unsafe impl #impl_generics ::chromium::StableLayout
+      for #name #ty_generics #where_clause {}
+  })
+}
+
+fn check_repr(input: &DeriveInput) -> Result<(), Error> {
+  let mut stable = false;
+  for attr in input.attrs.iter().filter(|a| a.path().is_ident("repr")) {
+    attr.parse_nested_meta(|meta| {
+      if meta.path.is_ident("C") || meta.path.is_ident("transparent") {
+        stable = true;
+      }
+      // skip any arguments, such as in `align(8)` or `packed(2)`
+      if meta.input.peek(syn::token::Paren) {
+        let content;
+        syn::parenthesized!(content in meta.input);
+        content.parse::<TokenStream2>()?;
+      }
+      Ok(())
+    })?;
+  }
+  if stable {
+    Ok(())
+  } else {
+    Err(Error::new(
+      Span::call_site(),
+      "`StableLayout` can only be derived for `repr(C)` or \
+       `repr(transparent)` structs",
+    ))
+  }
+}
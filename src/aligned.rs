@@ -0,0 +1,167 @@
+use core::{
+  fmt::Debug,
+  ops::{Deref, DerefMut},
+};
+
+use super::StableLayout;
+
+/// Marker types for use with [`Aligned`].
+///
+/// Each marker is a zero sized type with the alignment in its name.
+pub mod align {
+  use super::StableLayout;
+
+  /// A zero sized type that's only used for its alignment.
+  ///
+  /// This is sealed, use one of the marker types in this module.
+  pub trait Alignment: StableLayout + Copy + sealed::Sealed {
+    /// The alignment, in bytes.
+    const ALIGN: usize;
+  }
+
+  mod sealed {
+    pub trait Sealed {}
+  }
+
+  macro_rules! make_alignments {
+    ( $( $name:ident = $n:literal ),* ) => {
+      $(
+        #[doc = concat!("An alignment of ", stringify!($n), " bytes.")]
+        #[derive(Debug, Clone, Copy, Default)]
+        #[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[repr(C, align($n))]
+        pub struct $name;
+        unsafe impl StableLayout for $name {}
+        impl sealed::Sealed for $name {}
+        impl Alignment for $name {
+          const ALIGN: usize = $n;
+        }
+      )*
+    };
+  }
+  make_alignments!(
+    A1 = 1,
+    A2 = 2,
+    A4 = 4,
+    A8 = 8,
+    A16 = 16,
+    A32 = 32,
+    A64 = 64,
+    A128 = 128,
+    A256 = 256,
+    A512 = 512,
+    A1024 = 1024,
+    A2048 = 2048,
+    A4096 = 4096
+  );
+}
+
+use align::Alignment;
+
+/// A value with its alignment raised to at least that of `A`.
+///
+/// This is how you match a C struct member that's declared with `_Alignas(N)`,
+/// `alignas(N)`, `__attribute__((aligned(N)))`, or `__declspec(align(N))`,
+/// with `A` being the [marker type](align) for `N`.
+///
+/// If `T` already has a greater alignment than `A` then the alignment of `T`
+/// is kept, just like in C.
+///
+/// Note that a Rust type's size is always a multiple of its alignment, so the
+/// size of `Aligned<A, T>` is rounded up to the alignment. A C compiler
+/// doesn't pad an over-aligned member itself, only the struct as a whole. This
+/// means that the layouts only match if the member is either the last member
+/// or is followed by a member that's at least as aligned, which is how these
+/// attributes are usually used anyway.
+///
+/// ```rust
+/// # use chromium::*;
+/// # use chromium::align::*;
+/// let x: Aligned<A16, u32> = Aligned::new(7);
+/// assert_eq!(core::mem::align_of_val(&x), 16);
+/// assert_eq!(*x, 7);
+/// ```
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `Aligned<A16, u32>`
+/// typedef struct {
+///   _Alignas(16) uint32_t value;
+/// } Aligned_16_u32;
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(C)]
+pub struct Aligned<A, T>
+where
+  A: Alignment,
+{
+  align: [A; 0],
+  /// The aligned value.
+  pub value: T,
+}
+
+unsafe impl<A, T> StableLayout for Aligned<A, T>
+where
+  A: Alignment,
+  T: StableLayout,
+{
+}
+
+impl<A, T> Aligned<A, T>
+where
+  A: Alignment,
+{
+  /// Wraps a value.
+  #[inline(always)]
+  pub const fn new(value: T) -> Self {
+    Self { align: [], value }
+  }
+
+  /// Unwraps the value.
+  #[inline(always)]
+  pub fn into_inner(self) -> T {
+    self.value
+  }
+}
+
+impl<A, T> Debug for Aligned<A, T>
+where
+  A: Alignment,
+  T: Debug,
+{
+  /// Debug prints as the inner value would.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    Debug::fmt(&self.value, f)
+  }
+}
+
+impl<A, T> Deref for Aligned<A, T>
+where
+  A: Alignment,
+{
+  type Target = T;
+  #[inline(always)]
+  fn deref(&self) -> &T {
+    &self.value
+  }
+}
+
+impl<A, T> DerefMut for Aligned<A, T>
+where
+  A: Alignment,
+{
+  #[inline(always)]
+  fn deref_mut(&mut self) -> &mut T {
+    &mut self.value
+  }
+}
+
+impl<A, T> From<T> for Aligned<A, T>
+where
+  A: Alignment,
+{
+  #[inline(always)]
+  fn from(value: T) -> Self {
+    Self::new(value)
+  }
+}
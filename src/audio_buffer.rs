@@ -0,0 +1,292 @@
+use core::{fmt::Debug, iter::StepBy, ops::Range, slice};
+
+use super::{SharedSlice, StableLayout, UniqueSlice};
+
+// General Safety Note: The soundness of the audio buffer types is centered
+// around the fact that the fields are all private, and so *safe rust* must
+// construct values of the types from an existing slice that is checked to split
+// evenly into the channels. However, because the types are `repr(C)` they can
+// of course be constructed with unsafe rust, or even by foreign code. It is the
+// responsibility of _the other code_ to ensure that the actual fields are
+// valid.
+
+/// How the channels of an audio buffer are arranged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ChannelLayout {
+  /// One sample from each channel per frame, frame after frame (`LRLRLR`).
+  Interleaved = 0,
+  /// All the samples of each channel, channel after channel (`LLLRRR`).
+  Planar = 1,
+}
+
+unsafe impl StableLayout for ChannelLayout {}
+
+/// Works out the range and step of a channel's samples.
+#[inline(always)]
+fn channel_range_step(
+  layout: ChannelLayout, channels: usize, frames: usize, channel: usize,
+) -> (Range<usize>, usize) {
+  let (start, step) = match layout {
+    ChannelLayout::Interleaved => (channel, channels),
+    ChannelLayout::Planar => (channel * frames, 1),
+  };
+  if frames == 0 {
+    (0..0, step)
+  } else {
+    (start..start + (frames - 1) * step + 1, step)
+  }
+}
+
+/// Checks that a buffer of `len` samples splits evenly into `channels`.
+#[inline(always)]
+fn check_channels(len: usize, channels: usize) -> bool {
+  len.checked_rem(channels) == Some(0)
+}
+
+/// A **shared** view of `f32` audio samples with a stable layout.
+///
+/// This is a [`SharedSlice<f32>`](SharedSlice) plus the channel count and the
+/// [`ChannelLayout`], which is all that a plugin host and a plugin need to
+/// agree on to read a sample buffer.
+///
+/// ## Unsafety
+///
+/// Because this type is primarily intended to help _unsafe_ Rust we should
+/// discuss the precise guarantees offered:
+/// * **Validity Invariants**
+///   * The data layout is a `SharedSlice<'a, f32>`, a `usize` (`channels`),
+///     and then a `ChannelLayout`.
+///   * The `ChannelLayout` must be one of the declared values.
+/// * **Soundness Invariants**
+///   * The `SharedSlice` must uphold its own invariants.
+///   * `channels` must not be 0.
+///   * The number of samples must be a multiple of `channels`.
+///
+/// It's equivalent to the following C declaration:
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `SharedAudioBuffer<'a>`
+/// typedef struct {
+///   float const *ptr;
+///   uintptr_t len;
+///   uintptr_t channels;
+///   uint32_t layout;
+/// } SharedAudioBuffer;
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct SharedAudioBuffer<'a> {
+  samples: SharedSlice<'a, f32>,
+  channels: usize,
+  layout: ChannelLayout,
+}
+
+unsafe impl<'a> StableLayout for SharedAudioBuffer<'a> {}
+
+impl<'a> SharedAudioBuffer<'a> {
+  /// Makes an audio view over some samples.
+  ///
+  /// Gives `None` if `channels` is 0 or doesn't evenly divide the number of
+  /// samples.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let samples = [0.1, -0.1, 0.2, -0.2, 0.3, -0.3];
+  /// let audio = SharedAudioBuffer::new(
+  ///   SharedSlice::from(&samples[..]),
+  ///   2,
+  ///   ChannelLayout::Interleaved,
+  /// )
+  /// .unwrap();
+  /// assert_eq!(audio.frames(), 3);
+  /// assert!(audio.channel(1).unwrap().eq(&[-0.1, -0.2, -0.3]));
+  /// ```
+  #[inline]
+  pub fn new(
+    samples: SharedSlice<'a, f32>, channels: usize, layout: ChannelLayout,
+  ) -> Option<Self> {
+    if check_channels(samples.len(), channels) {
+      Some(Self { samples, channels, layout })
+    } else {
+      None
+    }
+  }
+
+  /// The number of channels.
+  #[inline(always)]
+  pub fn channels(&self) -> usize {
+    self.channels
+  }
+
+  /// The number of frames (samples per channel).
+  #[inline(always)]
+  pub fn frames(&self) -> usize {
+    self.samples.len().checked_div(self.channels).unwrap_or(0)
+  }
+
+  /// How the channels are arranged.
+  #[inline(always)]
+  pub fn layout(&self) -> ChannelLayout {
+    self.layout
+  }
+
+  /// All of the samples, in their stored order.
+  #[inline(always)]
+  pub fn samples(&self) -> SharedSlice<'a, f32> {
+    self.samples
+  }
+
+  /// Iterates over the samples of one channel, or gives `None` if the channel
+  /// is out of bounds.
+  #[inline]
+  pub fn channel(
+    &self, channel: usize,
+  ) -> Option<StepBy<slice::Iter<'a, f32>>> {
+    if channel >= self.channels {
+      return None;
+    }
+    let (range, step) =
+      channel_range_step(self.layout, self.channels, self.frames(), channel);
+    let samples: &'a [f32] = self.samples.into();
+    Some(samples.get(range)?.iter().step_by(step))
+  }
+}
+
+impl<'a> Debug for SharedAudioBuffer<'a> {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_struct("SharedAudioBuffer")
+      .field("channels", &self.channels)
+      .field("frames", &self.frames())
+      .field("layout", &self.layout)
+      .finish()
+  }
+}
+
+/// A **unique** view of `f32` audio samples with a stable layout.
+///
+/// This is the `UniqueSlice` version of [`SharedAudioBuffer`], for output
+/// buffers that a plugin fills in.
+///
+/// ## Unsafety
+///
+/// Because this type is primarily intended to help _unsafe_ Rust we should
+/// discuss the precise guarantees offered:
+/// * **Validity Invariants**
+///   * The data layout is a `UniqueSlice<'a, f32>`, a `usize` (`channels`),
+///     and then a `ChannelLayout`.
+///   * The `ChannelLayout` must be one of the declared values.
+/// * **Soundness Invariants**
+///   * The `UniqueSlice` must uphold its own invariants.
+///   * `channels` must not be 0.
+///   * The number of samples must be a multiple of `channels`.
+///
+/// It's equivalent to the following C declaration:
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `UniqueAudioBuffer<'a>`
+/// typedef struct {
+///   float *ptr;
+///   uintptr_t len;
+///   uintptr_t channels;
+///   uint32_t layout;
+/// } UniqueAudioBuffer;
+/// ```
+#[repr(C)]
+pub struct UniqueAudioBuffer<'a> {
+  samples: UniqueSlice<'a, f32>,
+  channels: usize,
+  layout: ChannelLayout,
+}
+
+unsafe impl<'a> StableLayout for UniqueAudioBuffer<'a> {}
+
+impl<'a> UniqueAudioBuffer<'a> {
+  /// Makes an audio view over some samples.
+  ///
+  /// Gives `None` if `channels` is 0 or doesn't evenly divide the number of
+  /// samples.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let mut samples = [0.0; 6];
+  /// let mut audio = UniqueAudioBuffer::new(
+  ///   UniqueSlice::from(&mut samples[..]),
+  ///   2,
+  ///   ChannelLayout::Planar,
+  /// )
+  /// .unwrap();
+  /// audio.channel_mut(1).unwrap().for_each(|s| *s = 1.0);
+  /// assert_eq!(samples, [0.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+  /// ```
+  #[inline]
+  pub fn new(
+    samples: UniqueSlice<'a, f32>, channels: usize, layout: ChannelLayout,
+  ) -> Option<Self> {
+    if check_channels(samples.len(), channels) {
+      Some(Self { samples, channels, layout })
+    } else {
+      None
+    }
+  }
+
+  /// The number of channels.
+  #[inline(always)]
+  pub fn channels(&self) -> usize {
+    self.channels
+  }
+
+  /// The number of frames (samples per channel).
+  #[inline(always)]
+  pub fn frames(&self) -> usize {
+    self.samples.len().checked_div(self.channels).unwrap_or(0)
+  }
+
+  /// How the channels are arranged.
+  #[inline(always)]
+  pub fn layout(&self) -> ChannelLayout {
+    self.layout
+  }
+
+  /// Reborrows this as a shared view.
+  #[inline]
+  pub fn as_shared(&self) -> SharedAudioBuffer<'_> {
+    SharedAudioBuffer {
+      samples: SharedSlice::from(&self.samples[..]),
+      channels: self.channels,
+      layout: self.layout,
+    }
+  }
+
+  /// All of the samples, in their stored order.
+  #[inline(always)]
+  pub fn samples_mut(&mut self) -> &mut [f32] {
+    &mut self.samples
+  }
+
+  /// Iterates over the samples of one channel, or gives `None` if the channel
+  /// is out of bounds.
+  #[inline]
+  pub fn channel_mut(
+    &mut self, channel: usize,
+  ) -> Option<StepBy<slice::IterMut<'_, f32>>> {
+    if channel >= self.channels {
+      return None;
+    }
+    let (range, step) =
+      channel_range_step(self.layout, self.channels, self.frames(), channel);
+    Some(self.samples.get_mut(range)?.iter_mut().step_by(step))
+  }
+}
+
+impl<'a> Debug for UniqueAudioBuffer<'a> {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_struct("UniqueAudioBuffer")
+      .field("channels", &self.channels)
+      .field("frames", &self.frames())
+      .field("layout", &self.layout)
+      .finish()
+  }
+}
@@ -0,0 +1,102 @@
+use core::{ffi::c_void, fmt::Debug};
+
+use super::{SharedSlice, SharedStr, StableLayout};
+
+/// One named capability published by a host.
+///
+/// The `layout_id` is a number that the host and plugin agree identifies the
+/// exact layout of whatever `ptr` points to, usually a vtable struct. Bump it
+/// whenever that layout changes, and a plugin built against the old layout
+/// won't find the new one by mistake.
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `CapabilityEntry<'a>`
+/// typedef struct {
+///   uint8_t const *name_ptr;
+///   uintptr_t name_len;
+///   uint64_t layout_id;
+///   void const *ptr;
+/// } CapabilityEntry;
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct CapabilityEntry<'a> {
+  /// The capability's name, such as `"renderer/v2"`.
+  pub name: SharedStr<'a>,
+  /// Identifies the layout of the data behind `ptr`.
+  pub layout_id: u64,
+  /// The capability itself.
+  pub ptr: *const c_void,
+}
+
+unsafe impl<'a> StableLayout for CapabilityEntry<'a> {}
+
+/// A table of named capabilities that a host publishes for plugins to query.
+///
+/// This is just a [`SharedSlice`] of [`CapabilityEntry`] values, so a host
+/// builds the table once and then hands out the registry by value.
+/// Lookups are a linear scan, since a host will only ever publish a handful of
+/// capabilities.
+///
+/// ```rust
+/// # use chromium::*;
+/// # use core::ffi::c_void;
+/// #[repr(C)]
+/// struct RendererV2 {
+///   draw: extern "C" fn(u32),
+/// }
+/// extern "C" fn draw(_: u32) {}
+/// static RENDERER: RendererV2 = RendererV2 { draw };
+///
+/// let entries = [CapabilityEntry {
+///   name: SharedStr::from("renderer/v2"),
+///   layout_id: 2,
+///   ptr: &RENDERER as *const RendererV2 as *const c_void,
+/// }];
+/// let registry = CapabilityRegistry::new(&entries);
+///
+/// assert!(registry.lookup("renderer/v2", 2).is_some());
+/// assert!(registry.lookup("renderer/v2", 1).is_none());
+/// assert!(registry.lookup("audio/v1", 1).is_none());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[repr(transparent)]
+pub struct CapabilityRegistry<'a> {
+  entries: SharedSlice<'a, CapabilityEntry<'a>>,
+}
+
+unsafe impl<'a> StableLayout for CapabilityRegistry<'a> {}
+
+impl<'a> CapabilityRegistry<'a> {
+  /// Makes a registry of the entries given.
+  #[inline(always)]
+  pub fn new(entries: &'a [CapabilityEntry<'a>]) -> Self {
+    Self { entries: SharedSlice::from(entries) }
+  }
+
+  /// All of the entries.
+  #[inline(always)]
+  pub fn entries(&self) -> &'a [CapabilityEntry<'a>] {
+    self.entries.into()
+  }
+
+  /// Finds the first entry with the given name, whatever its layout id.
+  #[inline]
+  pub fn find(&self, name: &str) -> Option<&'a CapabilityEntry<'a>> {
+    self.entries().iter().find(|e| &*e.name == name)
+  }
+
+  /// Gets the pointer of the entry with the given name and layout id.
+  ///
+  /// A host can publish more than one layout of the same capability under the
+  /// same name, and this gives whichever one matches.
+  #[inline]
+  pub fn lookup(&self, name: &str, layout_id: u64) -> Option<*const c_void> {
+    self
+      .entries()
+      .iter()
+      .find(|e| e.layout_id == layout_id && &*e.name == name)
+      .map(|e| e.ptr)
+  }
+}
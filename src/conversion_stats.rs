@@ -0,0 +1,152 @@
+#![cfg(feature = "conversion_stats")]
+
+use core::sync::atomic::{AtomicIsize, AtomicUsize, Ordering};
+
+/// Which stable type a conversion involved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum StatKind {
+  SharedSlice = 0,
+  UniqueSlice = 1,
+  SharedStr = 2,
+  UniqueStr = 3,
+  StableVec = 4,
+  StableString = 5,
+}
+
+const KIND_COUNT: usize = 6;
+
+/// Which way a conversion went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum StatDirection {
+  /// From the Rust form to the stable form.
+  Into,
+  /// From the stable form back to the Rust form.
+  OutOf,
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO: AtomicUsize = AtomicUsize::new(0);
+
+static INTO: [AtomicUsize; KIND_COUNT] = [ZERO; KIND_COUNT];
+static OUT_OF: [AtomicUsize; KIND_COUNT] = [ZERO; KIND_COUNT];
+static BYTES_INTO: AtomicUsize = AtomicUsize::new(0);
+static BYTES_OUT_OF: AtomicUsize = AtomicUsize::new(0);
+static OWNED_OUTSTANDING: AtomicIsize = AtomicIsize::new(0);
+static OWNED_PEAK: AtomicIsize = AtomicIsize::new(0);
+
+/// Records one conversion.
+#[inline]
+pub(crate) fn record(kind: StatKind, direction: StatDirection, bytes: usize) {
+  let owned = matches!(kind, StatKind::StableVec | StatKind::StableString);
+  match direction {
+    StatDirection::Into => {
+      INTO[kind as usize].fetch_add(1, Ordering::Relaxed);
+      BYTES_INTO.fetch_add(bytes, Ordering::Relaxed);
+      if owned {
+        let now = OWNED_OUTSTANDING.fetch_add(1, Ordering::Relaxed) + 1;
+        OWNED_PEAK.fetch_max(now, Ordering::Relaxed);
+      }
+    }
+    StatDirection::OutOf => {
+      OUT_OF[kind as usize].fetch_add(1, Ordering::Relaxed);
+      BYTES_OUT_OF.fetch_add(bytes, Ordering::Relaxed);
+      if owned {
+        OWNED_OUTSTANDING.fetch_sub(1, Ordering::Relaxed);
+      }
+    }
+  }
+}
+
+/// The number of conversions of one stable type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ConversionCount {
+  /// Conversions from the Rust form into the stable form.
+  pub into: usize,
+  /// Conversions from the stable form back out to the Rust form.
+  pub out_of: usize,
+}
+
+/// A snapshot of the conversion counters.
+///
+/// All counters are process wide (well, they're per copy of this crate), and
+/// wrap on overflow. The counters are updated with relaxed atomics, so a
+/// snapshot taken while other threads are converting values isn't guaranteed
+/// to be consistent between fields.
+///
+/// Internally, some `StableVec` and `StableString` methods briefly turn the
+/// value back into a `Vec` or `String` and then back again. Those round trips
+/// aren't counted, since the buffer never really leaves the stable value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ConversionStats {
+  /// `SharedSlice` conversions.
+  pub shared_slice: ConversionCount,
+  /// `UniqueSlice` conversions.
+  pub unique_slice: ConversionCount,
+  /// `SharedStr` conversions.
+  pub shared_str: ConversionCount,
+  /// `UniqueStr` conversions.
+  pub unique_str: ConversionCount,
+  /// `StableVec` conversions.
+  pub stable_vec: ConversionCount,
+  /// `StableString` conversions.
+  pub stable_string: ConversionCount,
+  /// Total bytes of data viewed or owned by values converted into stable form.
+  pub bytes_into: usize,
+  /// Total bytes of data viewed or owned by values converted out of stable
+  /// form.
+  pub bytes_out_of: usize,
+  /// Owned stable values (`StableVec` and `StableString`) made in this
+  /// process that haven't been converted back yet.
+  ///
+  /// This can go negative if owned values are made on the far side of the FFI
+  /// boundary and then converted back on this side.
+  pub owned_outstanding: isize,
+  /// The highest that `owned_outstanding` has been.
+  pub owned_peak: isize,
+}
+
+impl ConversionStats {
+  /// Takes a snapshot of the current counters.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let before = ConversionStats::snapshot();
+  /// let data = [1_u32, 2, 3];
+  /// let _shared = SharedSlice::from(&data[..]);
+  /// let after = ConversionStats::snapshot();
+  /// assert!(after.shared_slice.into > before.shared_slice.into);
+  /// assert!(after.bytes_into >= before.bytes_into + 12);
+  /// ```
+  pub fn snapshot() -> Self {
+    let count = |kind: StatKind| ConversionCount {
+      into: INTO[kind as usize].load(Ordering::Relaxed),
+      out_of: OUT_OF[kind as usize].load(Ordering::Relaxed),
+    };
+    Self {
+      shared_slice: count(StatKind::SharedSlice),
+      unique_slice: count(StatKind::UniqueSlice),
+      shared_str: count(StatKind::SharedStr),
+      unique_str: count(StatKind::UniqueStr),
+      stable_vec: count(StatKind::StableVec),
+      stable_string: count(StatKind::StableString),
+      bytes_into: BYTES_INTO.load(Ordering::Relaxed),
+      bytes_out_of: BYTES_OUT_OF.load(Ordering::Relaxed),
+      owned_outstanding: OWNED_OUTSTANDING.load(Ordering::Relaxed),
+      owned_peak: OWNED_PEAK.load(Ordering::Relaxed),
+    }
+  }
+
+  /// Sets all of the counters back to 0.
+  ///
+  /// The peak is reset to the current number of outstanding owned values,
+  /// which are themselves _not_ reset, since those values do still exist.
+  pub fn reset() {
+    for counter in INTO.iter().chain(OUT_OF.iter()) {
+      counter.store(0, Ordering::Relaxed);
+    }
+    BYTES_INTO.store(0, Ordering::Relaxed);
+    BYTES_OUT_OF.store(0, Ordering::Relaxed);
+    OWNED_PEAK
+      .store(OWNED_OUTSTANDING.load(Ordering::Relaxed), Ordering::Relaxed);
+  }
+}
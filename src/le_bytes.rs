@@ -0,0 +1,46 @@
+/// Element types that can be written as and read from little-endian bytes.
+///
+/// This is what lets the owned stable types serialize their _contents_ (as
+/// opposed to their pointers) in an explicit byte order, such as with
+/// `StableVec::to_le_bytes`. That way a snapshot taken on a big-endian machine
+/// can be read on a little-endian one.
+///
+/// `usize` and `isize` aren't included, since their size isn't the same on all
+/// machines.
+pub trait LeBytes: Copy {
+  /// The number of bytes in one value.
+  const SIZE: usize;
+
+  /// Writes the value into the first `SIZE` bytes of `out`.
+  ///
+  /// ## Panics
+  /// If `out` is shorter than `SIZE`.
+  fn write_le(self, out: &mut [u8]);
+
+  /// Reads a value from the first `SIZE` bytes of `bytes`.
+  ///
+  /// ## Panics
+  /// If `bytes` is shorter than `SIZE`.
+  fn read_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_le_bytes {
+  ( $( $t:ty ),* ) => {
+    $(
+      impl LeBytes for $t {
+        const SIZE: usize = core::mem::size_of::<$t>();
+        #[inline(always)]
+        fn write_le(self, out: &mut [u8]) {
+          out[..Self::SIZE].copy_from_slice(&self.to_le_bytes())
+        }
+        #[inline(always)]
+        fn read_le(bytes: &[u8]) -> Self {
+          let mut buf = [0; core::mem::size_of::<$t>()];
+          buf.copy_from_slice(&bytes[..Self::SIZE]);
+          Self::from_le_bytes(buf)
+        }
+      }
+    )*
+  };
+}
+impl_le_bytes!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
@@ -1,4 +1,12 @@
 #![no_std]
+#![cfg_attr(feature = "nightly_simd", feature(portable_simd))]
+#![cfg_attr(feature = "nightly_allocator_api", feature(allocator_api))]
+#![cfg_attr(feature = "nightly_try", feature(try_trait_v2))]
+#![cfg_attr(feature = "nightly_f16_f128", feature(f16, f128))]
+#![cfg_attr(
+  all(feature = "nightly_arm_neon", target_arch = "arm"),
+  feature(stdarch_arm_neon_intrinsics)
+)]
 #![warn(missing_docs)]
 
 //! Chromium helps add some stability to your metal.
@@ -22,9 +30,24 @@
 //! you're expected to just change the value back into the Rust form and use the
 //! "real" form of the data.
 //!
+//! ## Target Support
+//!
+//! The crate is `no_std` and works on targets of any pointer width, including
+//! 16-bit microcontrollers such as AVR and MSP430. Lengths are always `usize`,
+//! which is `uintptr_t` in C, so on a 16-bit target a slice's length is already
+//! 16 bits wide.
+//!
+//! The types that need atomic read-modify-write operations on pointer sized
+//! values (`StableRefCounted`, `StaticBuf`, `StableArcSlice`, and
+//! `StableArcStr`) only exist when `target_has_atomic = "ptr"`, and the
+//! `conversion_stats` feature is an error without it.
+//!
 //! ## Features
 //!
-//! * `unsafe_alloc` enables support for `Vec`, `String`, and `Box`.
+//! * `unsafe_alloc` enables support for `Vec`, `String`, and `Box`, as well as
+//!   the `StableRope` string builder, the `StableU32String` UTF-32 string, the
+//!   `StableBString` byte string, the `StableError` error report, and the
+//!   `StableCStrArray` argv builder.
 //!   * Note that in this case you **must not** transfer allocations between two
 //!     different global allocators.
 //!   * As of 2020-03-06 it _happens to be the case_ that the default global
@@ -33,31 +56,352 @@
 //!     library changes their global allocator things can break.
 //!   * This is a _brittle_ feature, not to be used lightly. That's why it says
 //!     "unsafe" right in the feature name.
+//! * `poison_on_release` makes `StableVec::take_vec` and
+//!   `StableString::take_string` leave a poison pattern behind, and makes
+//!   converting a poisoned value panic (or, with `no_panic`, give an empty
+//!   value). This is a debugging aid.
+//! * `trace_conversions` emits a [log](https://docs.rs/log) trace event,
+//!   with the `"chromium"` target, for every conversion into or out of a
+//!   stable type.
+//! * `conversion_stats` keeps counters of how many conversions of each stable
+//!   type happen, how many bytes they cover, and how many owned buffers are
+//!   outstanding. Read them with `ConversionStats::snapshot`.
+//! * `no_panic` is for code that can't unwind. It turns on the link time
+//!   check (`tests/test_no_panic.rs`, run with `--release`) that the
+//!   conversions between each stable type and its Rust type, and the `try_`
+//!   and `checked_` methods, can never panic. With `poison_on_release` it also
+//!   makes a poisoned value convert to an empty one instead of panicking. It
+//!   doesn't remove anything: methods such as `SharedSlice::windows`,
+//!   `StableVec::split_off`, and `StableResult::unwrap` still panic on bad
+//!   input, as do indexing out of bounds and allocating more than
+//!   `isize::MAX` bytes. Use the `try_` and `checked_` versions instead.
+//! * `derive` adds `#[derive(StableLayout)]`. It works on `repr(C)` and
+//!   `repr(transparent)` structs, and checks that every field is
+//!   `StableLayout` too.
+//! * `int128` adds `StableLayout` for `u128` and `i128`, as well as their
+//!   `NonZero` types and `Option`s of those. Rust only agrees with C about the
+//!   alignment of these types as of Rust 1.77, and not every C compiler has a
+//!   128-bit integer type on every target, so make sure that both sides of your
+//!   FFI boundary really have the same layout before you turn this on.
+//! * `anyhow`, along with `unsafe_alloc`, adds conversions between the `Error`
+//!   type of [anyhow](https://docs.rs/anyhow) and `StableError`, keeping the
+//!   message of every error in the chain.
+//! * `serde` adds `Serialize` from [serde](https://docs.rs/serde) for
+//!   `SharedSlice`, `UniqueSlice`, `SharedStr`, and `UniqueStr`, as well as
+//!   `Serialize` and `Deserialize` for `StableVec` and `StableString` when
+//!   `unsafe_alloc` is also enabled. Everything is serialized by content.
+//! * `arbitrary` adds `Arbitrary` from [arbitrary](https://docs.rs/arbitrary)
+//!   for `SharedSlice<u8>` and `SharedStr`, which borrow the fuzzer's input,
+//!   as well as for `StableVec` and `StableString` when `unsafe_alloc` is also
+//!   enabled. The `arbitrary` crate always uses the standard library.
+//! * `rayon` adds `IntoParallelIterator`, `ParallelSlice`, and (for
+//!   `UniqueSlice`) `ParallelSliceMut` from [rayon](https://docs.rs/rayon) for
+//!   `SharedSlice` and `UniqueSlice`, so `par_iter`, `par_chunks`, and the rest
+//!   work on them directly. The `rayon` crate always uses the standard
+//!   library.
+//! * `std` enables conversions with types from the standard library, such as
+//!   turning an `Instant` into a `StableInstant`.
+//! * `log`, along with `std`, adds `StableLogger`, a `log::Log` implementation
+//!   for plugins that forwards each [log](https://docs.rs/log) record to an
+//!   `extern "C"` callback as a `StableLogRecord`.
+//! * `tracing` adds `StableTraceLayer`, a
+//!   [tracing-subscriber](https://docs.rs/tracing-subscriber) layer for plugins
+//!   that forwards each span and event (with their metadata and fields) to
+//!   `extern "C"` callbacks as stable records. This also enables `std`.
+//! * `memmap2`, along with `std`, adds `MappedFile`, which memory maps a file
+//!   with [memmap2](https://docs.rs/memmap2) so that it can be shared as a
+//!   `SharedSlice<u8>` without reading it all into memory.
+//! * `bytes` adds `StableBytes`, which hands a [bytes](https://docs.rs/bytes)
+//!   `Bytes` across FFI without copying the data, along with a release
+//!   function for the far side to call when it's done. This doesn't need
+//!   `unsafe_alloc`, since the buffer is always freed by the Rust side.
+//! * `nalgebra` adds `StableLayout` for the statically sized matrix and vector
+//!   types of [nalgebra](https://docs.rs/nalgebra), as well as `Point`,
+//!   `Quaternion`, `Translation`, `Rotation`, and `Unit`.
+//! * `rgb` adds `StableLayout` for the pixel types of
+//!   [rgb](https://docs.rs/rgb), so you can send a `SharedSlice<RGBA8>` and
+//!   keep the pixel typing.
+//! * `fixed` adds `StableLayout` for the fixed-point number types of
+//!   [fixed](https://docs.rs/fixed) up to 64 bits, and the 128-bit ones too
+//!   when `int128` is also enabled.
+//! * `half` adds `StableLayout` for the `f16` and `bf16` types of
+//!   [half](https://docs.rs/half).
+//! * `ordered-float` adds `StableLayout` for `OrderedFloat` and `NotNan` from
+//!   [ordered-float](https://docs.rs/ordered-float).
+//! * `nightly_simd` adds `StableLayout` for `core::simd::Simd<T, N>` when the
+//!   lane count is a power of two. This requires Nightly.
+//! * `nightly_arm_neon` adds `StableLayout` for the NEON vector types of
+//!   `core::arch::arm`, when building for 32-bit ARM with NEON (ARMv7 or
+//!   later). This requires Nightly. The AArch64 NEON types are always covered.
+//! * `nightly_allocator_api` adds `StableVec::from_vec_in` and
+//!   `StableVec::into_vec_in`, which pass a custom allocator along explicitly,
+//!   and `StableVecIn`, which stores its allocator. This requires Nightly, and
+//!   also enables `unsafe_alloc`.
+//! * `nightly_f16_f128` adds `StableLayout` for the `f16` and `f128` float
+//!   types. This requires Nightly.
+//! * `nightly_try` lets `?` be used with `StableResult`. This requires Nightly.
+//! * `arrayvec` adds conversions from references to
+//!   [arrayvec](https://docs.rs/arrayvec)'s `ArrayVec` into `SharedSlice` and
+//!   `UniqueSlice`, and `TryFrom` from `SharedSlice` back into `ArrayVec`.
+//! * `smallvec` adds conversions from references to
+//!   [smallvec](https://docs.rs/smallvec)'s `SmallVec` into `SharedSlice` and
+//!   `UniqueSlice`, and between `SmallVec` and `StableVec` when `unsafe_alloc`
+//!   is also enabled. Converting into a `StableVec` moves inline elements onto
+//!   the heap, since only a heap allocation can be handed across FFI.
+//! * `tinyvec` adds conversions from references to
+//!   [tinyvec](https://docs.rs/tinyvec)'s `ArrayVec` into `SharedSlice` and
+//!   `UniqueSlice`. With `unsafe_alloc` it also covers `TinyVec`, and adds
+//!   conversions from both into `StableVec`, and from `StableVec` back into
+//!   `TinyVec`.
+//! * `heapless` adds conversions from references to the `Vec` and `String`
+//!   types of [heapless](https://docs.rs/heapless) into the borrowed types,
+//!   and `TryFrom` from `SharedSlice` and `SharedStr` back into them.
+//! * `widestring` adds conversions between the UTF-16 `U16Str` and `U16CStr`
+//!   types of [widestring](https://docs.rs/widestring) and `SharedSlice<u16>`
+//!   or `UniqueSlice<u16>`, and between `U16String` or `U16CString` and
+//!   `StableVec<u16>` when `unsafe_alloc` is also enabled. The C string types
+//!   keep their nul terminator in the slice.
+//! * `bstr` adds conversions between the `BStr` type of
+//!   [bstr](https://docs.rs/bstr) and `SharedBStr`, and between `BString` and
+//!   `StableBString` when `unsafe_alloc` is also enabled.
+//! * `embedded-dma` adds the `ReadBuffer` trait from
+//!   [embedded-dma](https://docs.rs/embedded-dma) for `'static` shared slices,
+//!   and both `ReadBuffer` and `WriteBuffer` for `'static` unique slices and
+//!   (with `unsafe_alloc`) `StableVec`, when the element type is a DMA `Word`.
+//!   These can be handed straight to HAL DMA transfers.
+//! * `chrono` and `time` add conversions between `StableTimestamp` and the UTC
+//!   `DateTime` of [chrono](https://docs.rs/chrono) or the `OffsetDateTime` of
+//!   [time](https://docs.rs/time).
+//! * `uuid` adds `StableLayout` for `Uuid` from [uuid](https://docs.rs/uuid).
+//! * `libc` adds `StableLayout` for the common [libc](https://docs.rs/libc)
+//!   structs that exist on every Unix target, such as `timespec`, `timeval`,
+//!   `iovec`, `stat`, and the `sockaddr` types. It does nothing on other
+//!   targets.
+//! * `windows-sys` adds `StableLayout` for common Win32 structs from
+//!   [windows-sys](https://docs.rs/windows-sys): `GUID`, `FILETIME`,
+//!   `SYSTEMTIME`, `LUID`, `POINT`, `SIZE`, `RECT`, `SECURITY_ATTRIBUTES`, and
+//!   `OVERLAPPED`.
+//! * `bitflags` adds the `impl_stable_layout_for_bitflags!` macro, which
+//!   marks types made with [bitflags](https://docs.rs/bitflags) as
+//!   `StableLayout`.
+//! * `bytemuck` adds the `impl_stable_layout_for_pod!` macro, which marks
+//!   [bytemuck](https://docs.rs/bytemuck) `Pod` types as `StableLayout`, as
+//!   well as `SharedSlice::try_cast` and `UniqueSlice::try_cast_mut` for
+//!   casting between slices of `Pod` types.
+//! * `glam` adds `StableLayout` for the `f32`, `f64`, `i32`, and `u32` vector,
+//!   matrix, quaternion, and affine transform types of
+//!   [glam](https://docs.rs/glam), such as `Vec3A` and `Mat4`.
+//! * `euclid` adds `StableLayout` for the points, vectors, sizes, rects, and
+//!   transforms of [euclid](https://docs.rs/euclid).
+//! * `subtle` adds `ConstantTimeEq` from [subtle](https://docs.rs/subtle) for
+//!   `SharedSlice`, `UniqueSlice`, and `StableVec`, so key material and tokens
+//!   can be compared without timing leaks.
+//! * `zeroize` adds `Zeroize` from [zeroize](https://docs.rs/zeroize) for
+//!   `UniqueSlice` and `UniqueStr`, as well as `StableVec` and `StableString`
+//!   when `unsafe_alloc` is also enabled.
 
-#[cfg(feature = "unsafe_alloc")]
+#[cfg(all(feature = "conversion_stats", not(target_has_atomic = "ptr")))]
+compile_error!("`conversion_stats` needs a target with pointer sized atomics");
+
+#[cfg(any(feature = "unsafe_alloc", feature = "bytes"))]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
+/// Emits a trace event for a conversion, when `trace_conversions` is enabled.
+macro_rules! trace_conversion {
+  ($src:ty => $dst:ty, $ptr:expr, $len:expr) => {
+    #[cfg(feature = "trace_conversions")]
+    log::trace!(
+      target: "chromium",
+      "{} -> {} (ptr: {:p}, len: {})",
+      core::any::type_name::<$src>(),
+      core::any::type_name::<$dst>(),
+      $ptr,
+      $len
+    );
+  };
+}
+
+/// Counts a conversion, when `conversion_stats` is enabled.
+macro_rules! count_conversion {
+  ($kind:ident, $direction:ident, $bytes:expr) => {
+    #[cfg(feature = "conversion_stats")]
+    crate::conversion_stats::record(
+      crate::conversion_stats::StatKind::$kind,
+      crate::conversion_stats::StatDirection::$direction,
+      $bytes,
+    );
+  };
+}
+
+mod poison;
+
 mod stable_layout;
 pub use stable_layout::*;
 
+#[cfg(feature = "derive")]
+pub use chromium_derive::StableLayout;
+
+mod le_bytes;
+pub use le_bytes::*;
+
+mod opaque_handle;
+
+mod aligned;
+pub use aligned::*;
+
 mod shared_slice;
 pub use shared_slice::*;
 
 mod unique_slice;
 pub use unique_slice::*;
 
+mod stable_cmp;
+
+mod stable_slice_iter;
+pub use stable_slice_iter::*;
+
+mod tagged_ptr;
+pub use tagged_ptr::*;
+
+mod out_buffer;
+pub use out_buffer::*;
+
+mod vol_slice;
+pub use vol_slice::*;
+
 mod shared_str;
 pub use shared_str::*;
 
 mod unique_str;
 pub use unique_str::*;
 
+mod shared_u32_str;
+pub use shared_u32_str::*;
+
+mod shared_b_str;
+pub use shared_b_str::*;
+
+mod shared_c_str_array;
+pub use shared_c_str_array::*;
+
+mod shared_matrix;
+pub use shared_matrix::*;
+
+mod audio_buffer;
+pub use audio_buffer::*;
+
+mod stable_instant;
+pub use stable_instant::*;
+
+mod stable_timestamp;
+pub use stable_timestamp::*;
+
+mod stable_status;
+pub use stable_status::*;
+
+mod stable_option;
+pub use stable_option::*;
+
+mod stable_result;
+pub use stable_result::*;
+
+mod capability_registry;
+pub use capability_registry::*;
+
+mod stable_feature_flags;
+pub use stable_feature_flags::*;
+
+#[cfg(target_has_atomic = "ptr")]
+mod stable_ref_counted;
+#[cfg(target_has_atomic = "ptr")]
+pub use stable_ref_counted::*;
+
+#[cfg(all(feature = "std", feature = "memmap2"))]
+mod mapped_file;
+#[cfg(all(feature = "std", feature = "memmap2"))]
+pub use mapped_file::*;
+
+#[cfg(feature = "bytes")]
+mod stable_bytes;
+#[cfg(feature = "bytes")]
+pub use stable_bytes::*;
+
+#[cfg(all(feature = "log", feature = "std"))]
+mod stable_logger;
+#[cfg(all(feature = "log", feature = "std"))]
+pub use stable_logger::*;
+
+#[cfg(feature = "tracing")]
+mod stable_trace_layer;
+#[cfg(feature = "tracing")]
+pub use stable_trace_layer::*;
+
+#[cfg(target_has_atomic = "ptr")]
+mod static_buf;
+#[cfg(target_has_atomic = "ptr")]
+pub use static_buf::*;
+
+#[cfg(feature = "conversion_stats")]
+mod conversion_stats;
+#[cfg(feature = "conversion_stats")]
+pub use conversion_stats::*;
+
 #[cfg(feature = "unsafe_alloc")]
 mod stable_vec;
 #[cfg(feature = "unsafe_alloc")]
 pub use stable_vec::*;
 
+#[cfg(all(feature = "unsafe_alloc", feature = "nightly_allocator_api"))]
+mod stable_vec_in;
+#[cfg(all(feature = "unsafe_alloc", feature = "nightly_allocator_api"))]
+pub use stable_vec_in::*;
+
 #[cfg(feature = "unsafe_alloc")]
 mod stable_string;
 #[cfg(feature = "unsafe_alloc")]
 pub use stable_string::*;
+
+#[cfg(feature = "unsafe_alloc")]
+mod stable_u32_string;
+#[cfg(feature = "unsafe_alloc")]
+pub use stable_u32_string::*;
+
+#[cfg(feature = "unsafe_alloc")]
+mod stable_b_string;
+#[cfg(feature = "unsafe_alloc")]
+pub use stable_b_string::*;
+
+#[cfg(feature = "unsafe_alloc")]
+mod stable_error;
+#[cfg(feature = "unsafe_alloc")]
+pub use stable_error::*;
+
+#[cfg(feature = "unsafe_alloc")]
+mod stable_c_str_array;
+#[cfg(feature = "unsafe_alloc")]
+pub use stable_c_str_array::*;
+
+#[cfg(all(feature = "unsafe_alloc", target_has_atomic = "ptr"))]
+mod stable_arc_slice;
+#[cfg(all(feature = "unsafe_alloc", target_has_atomic = "ptr"))]
+pub use stable_arc_slice::*;
+
+#[cfg(feature = "unsafe_alloc")]
+mod stable_rope;
+#[cfg(feature = "unsafe_alloc")]
+pub use stable_rope::*;
+
+#[cfg(feature = "bytemuck")]
+mod stable_bytemuck;
+#[cfg(feature = "bytemuck")]
+#[doc(hidden)]
+pub use bytemuck as __bytemuck;
+
+#[cfg(feature = "bitflags")]
+mod stable_bitflags;
+#[cfg(feature = "bitflags")]
+#[doc(hidden)]
+pub use bitflags as __bitflags;
@@ -0,0 +1,77 @@
+use core::fmt::Debug;
+use std::{fs::File, io, path::Path};
+
+use memmap2::Mmap;
+
+use super::SharedSlice;
+
+/// A read-only memory map of a file.
+///
+/// This owns the mapping, and hands out [`SharedSlice`] views of the file's
+/// bytes that borrow from it, so the mapping stays alive for as long as any
+/// view does. It's for sharing big read-only assets with a plugin without
+/// first reading them into a `Vec`.
+///
+/// This type itself is _not_ `repr(C)`. Keep it on the Rust side and pass the
+/// views across the FFI boundary.
+///
+/// ```rust
+/// # use chromium::*;
+/// let path = std::env::temp_dir().join("chromium_mapped_file_doctest");
+/// std::fs::write(&path, b"hello").unwrap();
+/// let mapped = unsafe { MappedFile::open(&path) }.unwrap();
+/// let view: SharedSlice<'_, u8> = mapped.as_shared();
+/// assert_eq!(&view[..], b"hello");
+/// # drop(mapped);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct MappedFile {
+  map: Mmap,
+}
+
+impl MappedFile {
+  /// Opens and maps the file at `path`.
+  ///
+  /// ## Safety
+  /// The file must not be modified (by this process or any other) while it's
+  /// mapped, since that would change memory that Rust assumes is immutable.
+  #[inline]
+  pub unsafe fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+    Self::from_file(&File::open(path)?)
+  }
+
+  /// Maps an already open file.
+  ///
+  /// The mapping doesn't need the `File` to stay open.
+  ///
+  /// ## Safety
+  /// The same as with [`open`](Self::open).
+  #[inline]
+  pub unsafe fn from_file(file: &File) -> io::Result<Self> {
+    Mmap::map(file).map(|map| Self { map })
+  }
+
+  /// The length of the file, in bytes.
+  #[inline(always)]
+  pub fn len(&self) -> usize {
+    self.map.len()
+  }
+
+  /// If the file is empty.
+  #[inline(always)]
+  pub fn is_empty(&self) -> bool {
+    self.map.is_empty()
+  }
+
+  /// Views the file's bytes.
+  #[inline(always)]
+  pub fn as_shared(&self) -> SharedSlice<'_, u8> {
+    SharedSlice::from(&self.map[..])
+  }
+}
+
+impl Debug for MappedFile {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_struct("MappedFile").field("len", &self.len()).finish()
+  }
+}
@@ -0,0 +1,141 @@
+/// Declares opaque handle newtypes for use over FFI.
+///
+/// Every FFI project ends up with a pile of handle types: values that the
+/// other side hands out and later takes back, but that your side never looks
+/// inside of. This macro declares them as `#[repr(transparent)]` newtypes that
+/// are [`StableLayout`](crate::StableLayout), `Copy`, `Eq`, `Hash`, and
+/// `Debug`.
+///
+/// There are three forms:
+/// * `struct Name(ptr);` wraps a `NonNull<c_void>`. There are `from_ptr` and
+///   `as_ptr` methods, and `Option<Name>` has the same layout as a pointer,
+///   with `None` as null.
+/// * `struct Name(niche T);` wraps an integer type `T` that has a niche, such
+///   as `NonZeroU32`. There are `from_raw` and `to_raw` methods, and the macro
+///   checks that `Option<Name>` has the same size as `T`, with `None` as 0.
+/// * `struct Name(T);` wraps any `StableLayout` integer type `T`. There are
+///   `from_raw` and `to_raw` methods.
+///
+/// The orphan rules don't allow your crate to implement `StableLayout` for
+/// `Option<Name>`, so an optional handle can go in an `extern "C"` function
+/// signature, but not in one of this crate's generic types.
+///
+/// ```rust
+/// # use chromium::*;
+/// # use core::{ffi::c_void, num::NonZeroU32};
+/// opaque_handle! {
+///   /// A window owned by the host.
+///   pub struct WindowHandle(ptr);
+///   /// A texture, 0 is never a valid id.
+///   pub struct TextureId(niche NonZeroU32);
+///   /// An entity in the scene.
+///   pub struct EntityId(u64);
+/// }
+///
+/// let mut x = 0_u8;
+/// let window = WindowHandle::from_ptr(&mut x as *mut u8 as *mut c_void);
+/// assert!(window.is_some());
+/// assert!(WindowHandle::from_ptr(core::ptr::null_mut()).is_none());
+///
+/// let tex = TextureId::from_raw(NonZeroU32::new(3).unwrap());
+/// assert_eq!(tex.to_raw().get(), 3);
+/// assert_eq!(
+///   core::mem::size_of::<Option<TextureId>>(),
+///   core::mem::size_of::<u32>()
+/// );
+///
+/// assert_eq!(format!("{:?}", EntityId::from_raw(7)), "EntityId(7)");
+/// ```
+#[macro_export]
+macro_rules! opaque_handle {
+  () => {};
+  (
+    $(#[$meta:meta])* $vis:vis struct $name:ident(ptr); $($rest:tt)*
+  ) => {
+    $(#[$meta])*
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    #[repr(transparent)]
+    $vis struct $name(::core::ptr::NonNull<::core::ffi::c_void>);
+
+    unsafe impl $crate::StableLayout for $name {}
+
+    impl $name {
+      /// Wraps a pointer, or gives `None` if it's null.
+      #[inline(always)]
+      pub fn from_ptr(
+        ptr: *mut ::core::ffi::c_void,
+      ) -> ::core::option::Option<Self> {
+        ::core::ptr::NonNull::new(ptr).map(Self)
+      }
+
+      /// Wraps a pointer without checking it.
+      ///
+      /// ## Safety
+      /// `ptr` must not be null.
+      #[inline(always)]
+      pub const unsafe fn new_unchecked(ptr: *mut ::core::ffi::c_void) -> Self {
+        Self(::core::ptr::NonNull::new_unchecked(ptr))
+      }
+
+      /// The wrapped pointer.
+      #[inline(always)]
+      pub const fn as_ptr(self) -> *mut ::core::ffi::c_void {
+        self.0.as_ptr()
+      }
+    }
+
+    impl ::core::fmt::Debug for $name {
+      fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        ::core::write!(f, "{}({:p})", stringify!($name), self.0)
+      }
+    }
+
+    $crate::opaque_handle!($($rest)*);
+  };
+  (
+    $(#[$meta:meta])* $vis:vis struct $name:ident(niche $t:ty); $($rest:tt)*
+  ) => {
+    $crate::opaque_handle!(@int $(#[$meta])* $vis struct $name($t));
+    const _: () = assert!(
+      ::core::mem::size_of::<::core::option::Option<$name>>()
+        == ::core::mem::size_of::<$t>()
+    );
+
+    $crate::opaque_handle!($($rest)*);
+  };
+  (
+    $(#[$meta:meta])* $vis:vis struct $name:ident($t:ty); $($rest:tt)*
+  ) => {
+    $crate::opaque_handle!(@int $(#[$meta])* $vis struct $name($t));
+
+    $crate::opaque_handle!($($rest)*);
+  };
+  (@int $(#[$meta:meta])* $vis:vis struct $name:ident($t:ty)) => {
+    $(#[$meta])*
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    #[repr(transparent)]
+    $vis struct $name($t);
+
+    unsafe impl $crate::StableLayout for $name where $t: $crate::StableLayout {}
+
+    impl $name {
+      /// Wraps a raw id.
+      #[inline(always)]
+      pub const fn from_raw(raw: $t) -> Self {
+        Self(raw)
+      }
+
+      /// The raw id.
+      #[inline(always)]
+      pub const fn to_raw(self) -> $t {
+        self.0
+      }
+    }
+
+    impl ::core::fmt::Debug for $name {
+      fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        ::core::write!(f, "{}({:?})", stringify!($name), self.0)
+      }
+    }
+  };
+}
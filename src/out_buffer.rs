@@ -0,0 +1,184 @@
+use core::{fmt::Debug, marker::PhantomData, mem::MaybeUninit, slice};
+
+use super::{StableLayout, UniqueSlice};
+
+#[cfg(feature = "unsafe_alloc")]
+use super::StableVec;
+#[cfg(feature = "unsafe_alloc")]
+use alloc::vec::Vec;
+
+// General Safety Note: The soundness of the `OutBuffer` type is centered
+// around the fact that the fields are all private, and so *safe rust* must
+// construct values of the type from an existing valid slice. However, because
+// the type is `repr(C)` it can of course be constructed with unsafe rust, or
+// even by foreign code. It is the responsibility of _the other code_ to ensure
+// that the actual fields are valid for being turned into a slice, and that the
+// filled count is only ever raised after the elements are written.
+
+/// A caller provided buffer for a callee to write output into.
+///
+/// This models the common "caller provides a buffer, callee reports how much
+/// it wrote" API shape. The caller makes an `OutBuffer` over some possibly
+/// uninitialized storage and passes it (usually by pointer) to the callee. The
+/// callee writes elements to the front of the storage and raises the `filled`
+/// count to match. Then the caller turns the buffer into a [`UniqueSlice`] of
+/// only the filled elements.
+///
+/// Dropping an `OutBuffer` never drops the filled elements, the same as with
+/// `MaybeUninit`.
+///
+/// ## Unsafety
+///
+/// Because this type is primarily intended to help _unsafe_ Rust we should
+/// discuss the precise guarantees offered:
+/// * **Validity Invariants**
+///   * The data layout is a `*mut T`, then a `usize` capacity, then a `usize`
+///     filled count.
+/// * **Soundness Invariants**
+///   * The `*mut T` must point to the start of a valid
+///     `&mut [MaybeUninit<T>]`.
+///   * The capacity must be the correct length of that slice.
+///   * The filled count must be no more than the capacity, and the first
+///     `filled` elements must be initialized.
+///   * For as long as the `OutBuffer` exists the memory in question has a
+///     unique borrow over it (tracked via `PhantomData`).
+///
+/// If your element type were `u8` then it would be equivalent layout to the
+/// following C declaration:
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `OutBuffer<'a, u8>`
+/// typedef struct {
+///   uint8_t *ptr;
+///   uintptr_t cap;
+///   uintptr_t filled; // raise this after writing!
+/// } OutBuffer_u8;
+/// ```
+#[repr(C)]
+pub struct OutBuffer<'a, T>
+where
+  T: StableLayout,
+{
+  ptr: *mut T,
+  cap: usize,
+  filled: usize,
+  life: PhantomData<&'a mut [MaybeUninit<T>]>,
+}
+
+unsafe impl<'a, T: StableLayout> StableLayout for OutBuffer<'a, T> {}
+
+impl<'a, T> OutBuffer<'a, T>
+where
+  T: StableLayout,
+{
+  /// Makes an empty out buffer over some storage.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// # use core::mem::MaybeUninit;
+  /// let mut storage = [MaybeUninit::<u32>::uninit(); 4];
+  /// let mut out = OutBuffer::new(&mut storage);
+  /// // this would usually be done by the callee
+  /// assert!(out.push(7).is_ok());
+  /// assert!(out.push(8).is_ok());
+  /// assert_eq!(out.filled(), 2);
+  /// let written = out.into_unique();
+  /// assert_eq!(&written[..], &[7, 8]);
+  /// ```
+  #[inline(always)]
+  pub fn new(storage: &'a mut [MaybeUninit<T>]) -> Self {
+    let ptr = storage.as_mut_ptr() as *mut T;
+    Self { ptr, cap: storage.len(), filled: 0, life: PhantomData }
+  }
+
+  /// The total number of elements the buffer can hold.
+  #[inline(always)]
+  pub fn capacity(&self) -> usize {
+    self.cap
+  }
+
+  /// The number of elements written so far.
+  #[inline(always)]
+  pub fn filled(&self) -> usize {
+    self.filled
+  }
+
+  /// The elements written so far.
+  #[inline(always)]
+  pub fn filled_slice(&self) -> &[T] {
+    // Safety: See note at the top of the module.
+    unsafe { slice::from_raw_parts(self.ptr, self.filled) }
+  }
+
+  /// The storage past the filled elements.
+  ///
+  /// Write to the front of this and then call
+  /// [`set_filled`](Self::set_filled).
+  #[inline(always)]
+  pub fn spare_mut(&mut self) -> &mut [MaybeUninit<T>] {
+    // Safety: See note at the top of the module.
+    unsafe {
+      slice::from_raw_parts_mut(
+        self.ptr.add(self.filled) as *mut MaybeUninit<T>,
+        self.cap - self.filled,
+      )
+    }
+  }
+
+  /// Writes an element after the filled ones, or gives it back if the buffer
+  /// is full.
+  #[inline]
+  pub fn push(&mut self, value: T) -> Result<(), T> {
+    match self.spare_mut().first_mut() {
+      Some(slot) => {
+        slot.write(value);
+        self.filled += 1;
+        Ok(())
+      }
+      None => Err(value),
+    }
+  }
+
+  /// Sets the filled count.
+  ///
+  /// ## Safety
+  /// * `filled` must be no more than the capacity.
+  /// * The first `filled` elements must be initialized.
+  #[inline(always)]
+  pub unsafe fn set_filled(&mut self, filled: usize) {
+    self.filled = filled;
+  }
+
+  /// Turns this into a view of only the filled elements.
+  #[inline(always)]
+  pub fn into_unique(self) -> UniqueSlice<'a, T> {
+    // Safety: See note at the top of the module.
+    UniqueSlice::from(unsafe {
+      slice::from_raw_parts_mut(self.ptr, self.filled)
+    })
+  }
+
+  /// Clones the filled elements into a new `StableVec`.
+  #[cfg(feature = "unsafe_alloc")]
+  #[inline]
+  pub fn to_stable_vec(&self) -> StableVec<T>
+  where
+    T: Clone,
+  {
+    StableVec::from(Vec::from(self.filled_slice()))
+  }
+}
+
+impl<'a, T: Debug> Debug for OutBuffer<'a, T>
+where
+  T: StableLayout,
+{
+  /// Debug prints the filled elements and the capacity.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_struct("OutBuffer")
+      .field("filled", &self.filled_slice())
+      .field("cap", &self.cap)
+      .finish()
+  }
+}
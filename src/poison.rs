@@ -0,0 +1,27 @@
+#![cfg(all(feature = "poison_on_release", feature = "unsafe_alloc"))]
+
+use core::mem::size_of;
+
+/// The bit pattern written over the fields of a released owned value.
+///
+/// As a pointer on 64-bit targets this is non-canonical, so dereferencing it
+/// faults right away instead of reading whatever memory happens to be there.
+pub(crate) const POISON: usize =
+  usize::from_ne_bytes([0xA5; size_of::<usize>()]);
+
+/// Checks if a value's pointer field has been poisoned.
+///
+/// Normally a poisoned value panics. With `no_panic` this gives `true` instead,
+/// and the caller treats the value as empty.
+#[inline]
+#[track_caller]
+pub(crate) fn check<T>(ptr: *const T, type_name: &str) -> bool {
+  let poisoned = ptr as usize == POISON;
+  #[cfg(not(feature = "no_panic"))]
+  if poisoned {
+    panic!("{} was used after it was released", type_name);
+  }
+  #[cfg(feature = "no_panic")]
+  let _ = type_name;
+  poisoned
+}
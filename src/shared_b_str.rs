@@ -0,0 +1,209 @@
+use core::{
+  fmt::{Debug, Display, Write},
+  marker::PhantomData,
+  ops::Deref,
+  slice, str,
+};
+
+use super::{SharedSlice, SharedStr, StableLayout};
+
+// General Safety Note: The soundness of the `SharedBStr` type is centered
+// around the fact that the fields are all private, and so *safe rust* must
+// construct values of the type from an existing valid slice. However, because
+// the type is `repr(C)` it can of course be constructed with unsafe rust, or
+// even by foreign code. It is the responsibility of _the other code_ to ensure
+// that the actual fields are valid for being turned into a slice.
+
+/// A struct for **shared** byte string views with a stable layout.
+///
+/// This is text that's _conventionally_ UTF-8, but isn't guaranteed to be,
+/// such as file paths or log lines from C code. It's the same layout as a
+/// [`SharedStr`] but without the UTF-8 requirement. `Display` replaces any
+/// invalid UTF-8 with U+FFFD (`�`), and `Debug` prints invalid bytes as `\xNN`
+/// escapes.
+///
+/// ## Unsafety
+///
+/// Because this type is primarily intended to help _unsafe_ Rust we should
+/// discuss the precise guarantees offered:
+/// * **Validity Invariants**
+///   * The data layout is a `*const u8` and then a `usize`.
+/// * **Soundness Invariants**
+///   * The `*const u8` must point to the start of a valid `&[u8]`.
+///   * The `usize` must be the correct length of that valid `&[u8]`.
+///   * For as long as the `SharedBStr` exists the memory in question has a
+///     shared borrow over it (tracked via `PhantomData`).
+///
+/// This type matches up with the following C layout:
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `SharedBStr<'a>`
+/// typedef struct {
+///   uint8_t const *ptr;
+///   uintptr_t len;
+/// } SharedBStr;
+/// ```
+#[repr(C)]
+pub struct SharedBStr<'a> {
+  ptr: *const u8,
+  len: usize,
+  life: PhantomData<&'a [u8]>,
+}
+
+unsafe impl<'a> StableLayout for SharedBStr<'a> {}
+
+impl<'a> SharedBStr<'a> {
+  /// Views the bytes as a string, if they're valid UTF-8.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// assert_eq!(&*SharedBStr::from("hi").to_str().unwrap(), "hi");
+  /// assert!(SharedBStr::from(&b"\xFF"[..]).to_str().is_err());
+  /// ```
+  #[inline]
+  pub fn to_str(&self) -> Result<SharedStr<'a>, str::Utf8Error> {
+    SharedStr::from_utf8((*self).into())
+  }
+
+  /// Splits the bytes into string pieces, with each invalid UTF-8 sequence
+  /// replaced by U+FFFD (`�`).
+  ///
+  /// See [`SharedStr::lossy_pieces`].
+  #[inline]
+  pub fn lossy_pieces(&self) -> impl Iterator<Item = SharedStr<'a>> + 'a {
+    SharedStr::lossy_pieces((*self).into())
+  }
+
+  /// Views the bytes as a plain byte slice.
+  #[inline(always)]
+  pub fn as_shared_slice(&self) -> SharedSlice<'a, u8> {
+    let bytes: &'a [u8] = (*self).into();
+    SharedSlice::from(bytes)
+  }
+}
+
+impl<'a> Debug for SharedBStr<'a> {
+  /// Debug prints as a `str` would, with invalid bytes as `\xNN` escapes.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let b = SharedBStr::from(&b"a\"b\xFF"[..]);
+  /// assert_eq!(format!("{:?}", b), r#""a\"b\xFF""#);
+  /// ```
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.write_char('"')?;
+    for chunk in self.utf8_chunks() {
+      for c in chunk.valid().chars() {
+        for e in c.escape_debug() {
+          f.write_char(e)?;
+        }
+      }
+      for b in chunk.invalid() {
+        write!(f, "\\x{:02X}", b)?;
+      }
+    }
+    f.write_char('"')
+  }
+}
+
+impl<'a> Display for SharedBStr<'a> {
+  /// Display prints as a `str` would, with each invalid UTF-8 sequence
+  /// replaced by U+FFFD (`�`).
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let b = SharedBStr::from(&b"ab\xFFcd"[..]);
+  /// assert_eq!(format!("{}", b), "ab\u{FFFD}cd");
+  /// ```
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    self.lossy_pieces().try_for_each(|piece| f.write_str(&piece))
+  }
+}
+
+impl<'a> Clone for SharedBStr<'a> {
+  #[inline(always)]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<'a> Copy for SharedBStr<'a> {}
+
+impl<'a> Default for SharedBStr<'a> {
+  /// Defaults to an empty string.
+  #[inline(always)]
+  fn default() -> Self {
+    let life = PhantomData;
+    let len = 0;
+    let ptr = core::ptr::NonNull::dangling().as_ptr();
+    Self { ptr, len, life }
+  }
+}
+
+impl<'a> Deref for SharedBStr<'a> {
+  type Target = [u8];
+  #[inline(always)]
+  fn deref(&self) -> &[u8] {
+    // Safety: See note at the top of the module.
+    unsafe { slice::from_raw_parts(self.ptr, self.len) }
+  }
+}
+
+impl<'a> From<&'a [u8]> for SharedBStr<'a> {
+  #[inline(always)]
+  fn from(b: &'a [u8]) -> Self {
+    trace_conversion!(&'a [u8] => Self, b.as_ptr(), b.len());
+    let life = PhantomData;
+    let len = b.len();
+    let ptr = b.as_ptr();
+    Self { ptr, len, life }
+  }
+}
+
+impl<'a> From<&'a str> for SharedBStr<'a> {
+  #[inline(always)]
+  fn from(s: &'a str) -> Self {
+    Self::from(s.as_bytes())
+  }
+}
+
+impl<'a> From<SharedStr<'a>> for SharedBStr<'a> {
+  #[inline(always)]
+  fn from(s: SharedStr<'a>) -> Self {
+    let s: &'a str = s.into();
+    Self::from(s)
+  }
+}
+
+impl<'a> From<SharedBStr<'a>> for &'a [u8] {
+  #[inline(always)]
+  fn from(shared: SharedBStr<'a>) -> Self {
+    trace_conversion!(SharedBStr<'a> => Self, shared.ptr, shared.len);
+    // Safety: See note at the top of the module.
+    unsafe { slice::from_raw_parts(shared.ptr, shared.len) }
+  }
+}
+
+#[cfg(feature = "bstr")]
+impl<'a> From<&'a bstr::BStr> for SharedBStr<'a> {
+  #[inline(always)]
+  fn from(b: &'a bstr::BStr) -> Self {
+    Self::from(&**b)
+  }
+}
+
+#[cfg(feature = "bstr")]
+impl<'a> From<SharedBStr<'a>> for &'a bstr::BStr {
+  /// ```rust
+  /// # use chromium::*;
+  /// use bstr::{BStr, ByteSlice};
+  /// let shared = SharedBStr::from(b"a\xFFb".as_bstr());
+  /// let back: &BStr = shared.into();
+  /// assert_eq!(back, b"a\xFFb".as_bstr());
+  /// ```
+  #[inline(always)]
+  fn from(shared: SharedBStr<'a>) -> Self {
+    let bytes: &'a [u8] = shared.into();
+    bstr::BStr::new(bytes)
+  }
+}
@@ -0,0 +1,126 @@
+use core::{
+  ffi::{c_char, CStr},
+  fmt::Debug,
+  marker::PhantomData,
+};
+
+use super::StableLayout;
+
+// General Safety Note: The soundness of the `SharedCStrArray` type is centered
+// around the fact that the field is private, and so *safe rust* must construct
+// values of the type from an existing valid array. However, because the type
+// is `repr(transparent)` it can of course be constructed with unsafe rust, or
+// even by foreign code. It is the responsibility of _the other code_ to ensure
+// that the pointer is valid for being walked as described below.
+
+/// A struct for **shared** views of a null terminated array of C strings.
+///
+/// This is the `char *const *` shape used by `argv` and `envp`: a pointer to
+/// an array of string pointers, with a null pointer marking the end of the
+/// array. Each string is itself null terminated.
+///
+/// ## Unsafety
+///
+/// Because this type is primarily intended to help _unsafe_ Rust we should
+/// discuss the precise guarantees offered:
+/// * **Validity Invariants**
+///   * The data layout is a single `*const *const c_char`.
+/// * **Soundness Invariants**
+///   * The pointer must point to an array of string pointers that ends with a
+///     null pointer.
+///   * Every non-null pointer in the array must point to a null terminated
+///     string.
+///   * For as long as the `SharedCStrArray` exists the memory in question has
+///     a shared borrow over it (tracked via `PhantomData`).
+///
+/// This type matches up with the following C layout:
+/// ```c
+/// // Identical layout to `SharedCStrArray<'a>`
+/// typedef char const *const *SharedCStrArray;
+/// ```
+#[repr(transparent)]
+pub struct SharedCStrArray<'a> {
+  ptr: *const *const c_char,
+  life: PhantomData<&'a CStr>,
+}
+
+unsafe impl<'a> StableLayout for SharedCStrArray<'a> {}
+
+impl<'a> SharedCStrArray<'a> {
+  /// Views a null terminated array of C strings.
+  ///
+  /// ## Safety
+  /// * `ptr` must meet all the soundness invariants described in the type's
+  ///   docs, for all of the lifetime `'a`.
+  #[inline(always)]
+  pub const unsafe fn from_ptr(ptr: *const *const c_char) -> Self {
+    Self { ptr, life: PhantomData }
+  }
+
+  /// The pointer to the start of the array.
+  #[inline(always)]
+  pub const fn as_ptr(&self) -> *const *const c_char {
+    self.ptr
+  }
+
+  /// Iterates over the strings.
+  ///
+  /// Counting the strings takes a walk over the whole array, the same as
+  /// `strlen` does for a single string.
+  #[inline(always)]
+  pub fn iter(&self) -> SharedCStrArrayIter<'a> {
+    SharedCStrArrayIter { ptr: self.ptr, life: PhantomData }
+  }
+}
+
+impl<'a> Debug for SharedCStrArray<'a> {
+  /// Debug prints as a list of strings.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
+
+impl<'a> Clone for SharedCStrArray<'a> {
+  #[inline(always)]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<'a> Copy for SharedCStrArray<'a> {}
+
+impl<'a> IntoIterator for SharedCStrArray<'a> {
+  type Item = &'a CStr;
+  type IntoIter = SharedCStrArrayIter<'a>;
+  #[inline(always)]
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+/// An iterator over the strings of a [`SharedCStrArray`].
+#[derive(Debug, Clone)]
+pub struct SharedCStrArrayIter<'a> {
+  ptr: *const *const c_char,
+  life: PhantomData<&'a CStr>,
+}
+
+impl<'a> Iterator for SharedCStrArrayIter<'a> {
+  type Item = &'a CStr;
+  #[inline]
+  fn next(&mut self) -> Option<&'a CStr> {
+    // Safety: See note at the top of the module. We never step past the
+    // terminating null.
+    unsafe {
+      let s = *self.ptr;
+      if s.is_null() {
+        None
+      } else {
+        self.ptr = self.ptr.add(1);
+        Some(CStr::from_ptr(s))
+      }
+    }
+  }
+}
+
+impl<'a> core::iter::FusedIterator for SharedCStrArrayIter<'a> {}
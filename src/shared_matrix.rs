@@ -0,0 +1,331 @@
+use core::{fmt::Debug, iter::StepBy, marker::PhantomData, slice};
+
+use super::{SharedSlice, StableLayout};
+
+// General Safety Note: The soundness of the `SharedMatrix` type is centered
+// around the fact that the fields are all private, and so *safe rust* must
+// construct values of the type from an existing valid slice that is checked to
+// be long enough. However, because the type is `repr(C)` it can of course be
+// constructed with unsafe rust, or even by foreign code. It is the
+// responsibility of _the other code_ to ensure that the actual fields are
+// valid.
+
+/// The storage order of a [`SharedMatrix`].
+///
+/// The values match `CBLAS_ORDER`, so this can be passed directly to CBLAS
+/// style functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum MatrixOrder {
+  /// Each row is contiguous, and the leading dimension is the distance between
+  /// the start of one row and the start of the next.
+  RowMajor = 101,
+  /// Each column is contiguous, and the leading dimension is the distance
+  /// between the start of one column and the start of the next.
+  ColMajor = 102,
+}
+
+unsafe impl StableLayout for MatrixOrder {}
+
+/// A struct for **shared** BLAS style matrix views with a stable layout.
+///
+/// The matrix has `rows` by `cols` elements stored in either row-major or
+/// column-major order. The leading dimension (`ld`, often called `lda` in BLAS
+/// docs) is the distance, in elements, between the start of one row (or
+/// column) and the next. It can be larger than the row (or column) length,
+/// which lets a view describe a sub-matrix of a larger matrix.
+///
+/// ## Unsafety
+///
+/// Because this type is primarily intended to help _unsafe_ Rust we should
+/// discuss the precise guarantees offered:
+/// * **Validity Invariants**
+///   * The data layout is a `*const T`, three `usize` values (`rows`, `cols`,
+///     `ld`), and then a `MatrixOrder`.
+///   * The `MatrixOrder` must be one of the declared values.
+/// * **Soundness Invariants**
+///   * The `*const T` must point to the start of a valid `&[T]`.
+///   * The `ld` must be at least the length of a row (row-major) or column
+///     (column-major).
+///   * If neither `rows` nor `cols` is 0, the slice must be long enough to hold
+///     the last element of the matrix.
+///   * For as long as the `SharedMatrix` exists the memory in question has a
+///     shared borrow over it (tracked via `PhantomData`).
+///
+/// If your element type were `f64` then it would be equivalent layout to the
+/// following C declaration:
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `SharedMatrix<'a, f64>`
+/// typedef struct {
+///   double const *ptr;
+///   uintptr_t rows;
+///   uintptr_t cols;
+///   uintptr_t ld;
+///   uint32_t order;
+/// } SharedMatrix_f64;
+/// ```
+#[repr(C)]
+pub struct SharedMatrix<'a, T>
+where
+  T: StableLayout,
+{
+  ptr: *const T,
+  rows: usize,
+  cols: usize,
+  ld: usize,
+  order: MatrixOrder,
+  life: PhantomData<&'a [T]>,
+}
+
+unsafe impl<'a, T: StableLayout> StableLayout for SharedMatrix<'a, T> {}
+
+impl<'a, T> Clone for SharedMatrix<'a, T>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<'a, T> Copy for SharedMatrix<'a, T> where T: StableLayout {}
+
+impl<'a, T> SharedMatrix<'a, T>
+where
+  T: StableLayout,
+{
+  /// Makes a matrix view over `data`.
+  ///
+  /// Gives `None` if `ld` is smaller than the length of a row (row-major) or
+  /// column (column-major), or if `data` is too short to hold the matrix.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// // A 2x2 column-major sub-matrix of a 3x2 matrix.
+  /// let data = [1.0, 2.0, 0.0, 3.0, 4.0, 0.0];
+  /// let m = SharedMatrix::new(&data, 2, 2, 3, MatrixOrder::ColMajor).unwrap();
+  /// assert_eq!(m.get(1, 0), Some(&2.0));
+  /// assert_eq!(&m.col_slice(1).unwrap()[..], &[3.0, 4.0]);
+  /// assert!(m.row(0).unwrap().eq(&[1.0, 3.0]));
+  /// ```
+  #[inline]
+  pub fn new(
+    data: &'a [T], rows: usize, cols: usize, ld: usize, order: MatrixOrder,
+  ) -> Option<Self> {
+    let (major, minor) = match order {
+      MatrixOrder::RowMajor => (rows, cols),
+      MatrixOrder::ColMajor => (cols, rows),
+    };
+    if ld < minor {
+      return None;
+    }
+    if major != 0 && minor != 0 {
+      let needed = (major - 1).checked_mul(ld)?.checked_add(minor)?;
+      if data.len() < needed {
+        return None;
+      }
+    }
+    let life = PhantomData;
+    let ptr = data.as_ptr();
+    Some(Self { ptr, rows, cols, ld, order, life })
+  }
+
+  /// The number of rows.
+  #[inline(always)]
+  pub fn rows(&self) -> usize {
+    self.rows
+  }
+
+  /// The number of columns.
+  #[inline(always)]
+  pub fn cols(&self) -> usize {
+    self.cols
+  }
+
+  /// The leading dimension.
+  #[inline(always)]
+  pub fn ld(&self) -> usize {
+    self.ld
+  }
+
+  /// The storage order.
+  #[inline(always)]
+  pub fn order(&self) -> MatrixOrder {
+    self.order
+  }
+
+  /// The pointer to the first element, suitable for passing to C.
+  #[inline(always)]
+  pub fn as_ptr(&self) -> *const T {
+    self.ptr
+  }
+
+  /// The underlying data, from the first element to the last element.
+  #[inline]
+  fn data(&self) -> &'a [T] {
+    let (major, minor) = self.major_minor();
+    let len =
+      if major == 0 || minor == 0 { 0 } else { (major - 1) * self.ld + minor };
+    // Safety: See note at the top of the module.
+    unsafe { slice::from_raw_parts(self.ptr, len) }
+  }
+
+  #[inline(always)]
+  fn major_minor(&self) -> (usize, usize) {
+    match self.order {
+      MatrixOrder::RowMajor => (self.rows, self.cols),
+      MatrixOrder::ColMajor => (self.cols, self.rows),
+    }
+  }
+
+  /// The index into the data of the given row and column.
+  #[inline(always)]
+  fn index_of(&self, row: usize, col: usize) -> usize {
+    match self.order {
+      MatrixOrder::RowMajor => row * self.ld + col,
+      MatrixOrder::ColMajor => col * self.ld + row,
+    }
+  }
+
+  /// Gets the element at the given row and column, if it's in bounds.
+  #[inline]
+  pub fn get(&self, row: usize, col: usize) -> Option<&'a T> {
+    if row < self.rows && col < self.cols {
+      self.data().get(self.index_of(row, col))
+    } else {
+      None
+    }
+  }
+
+  /// Gets a row as a contiguous slice.
+  ///
+  /// Gives `None` if the row is out of bounds, or if the matrix is
+  /// column-major (and so the row isn't contiguous). Use
+  /// [`row`](Self::row) for a row of either order.
+  #[inline]
+  pub fn row_slice(&self, row: usize) -> Option<SharedSlice<'a, T>> {
+    match self.order {
+      MatrixOrder::RowMajor if row < self.rows => {
+        Some(self.contiguous(self.index_of(row, 0), self.cols))
+      }
+      _ => None,
+    }
+  }
+
+  /// Gets a column as a contiguous slice.
+  ///
+  /// Gives `None` if the column is out of bounds, or if the matrix is
+  /// row-major (and so the column isn't contiguous). Use
+  /// [`col`](Self::col) for a column of either order.
+  #[inline]
+  pub fn col_slice(&self, col: usize) -> Option<SharedSlice<'a, T>> {
+    match self.order {
+      MatrixOrder::ColMajor if col < self.cols => {
+        Some(self.contiguous(self.index_of(0, col), self.rows))
+      }
+      _ => None,
+    }
+  }
+
+  #[inline]
+  fn contiguous(&self, start: usize, len: usize) -> SharedSlice<'a, T> {
+    if len == 0 {
+      SharedSlice::default()
+    } else {
+      SharedSlice::from(self.data().get(start..start + len).unwrap_or(&[]))
+    }
+  }
+
+  /// Iterates over a row, or gives `None` if the row is out of bounds.
+  #[inline]
+  pub fn row(&self, row: usize) -> Option<MatrixLine<'a, T>> {
+    if row >= self.rows {
+      return None;
+    }
+    let step = match self.order {
+      MatrixOrder::RowMajor => 1,
+      MatrixOrder::ColMajor => self.ld,
+    };
+    Some(self.line(self.index_of(row, 0), step, self.cols))
+  }
+
+  /// Iterates over a column, or gives `None` if the column is out of bounds.
+  #[inline]
+  pub fn col(&self, col: usize) -> Option<MatrixLine<'a, T>> {
+    if col >= self.cols {
+      return None;
+    }
+    let step = match self.order {
+      MatrixOrder::RowMajor => self.ld,
+      MatrixOrder::ColMajor => 1,
+    };
+    Some(self.line(self.index_of(0, col), step, self.rows))
+  }
+
+  #[inline]
+  fn line(&self, start: usize, step: usize, count: usize) -> MatrixLine<'a, T> {
+    let line: &'a [T] = if count == 0 {
+      &[]
+    } else {
+      self.data().get(start..start + (count - 1) * step + 1).unwrap_or(&[])
+    };
+    MatrixLine { iter: line.iter().step_by(step.max(1)) }
+  }
+}
+
+impl<'a, T: Debug> Debug for SharedMatrix<'a, T>
+where
+  T: StableLayout,
+{
+  /// Debug prints as a list of rows.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_list()
+      .entries((0..self.rows).map(|r| DebugLine(self.row(r).unwrap())))
+      .finish()
+  }
+}
+
+struct DebugLine<'a, T: StableLayout>(MatrixLine<'a, T>);
+impl<'a, T: Debug + StableLayout> Debug for DebugLine<'a, T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_list().entries(self.0.clone()).finish()
+  }
+}
+
+/// An iterator over one row or column of a [`SharedMatrix`].
+pub struct MatrixLine<'a, T>
+where
+  T: StableLayout,
+{
+  iter: StepBy<slice::Iter<'a, T>>,
+}
+
+impl<'a, T> Clone for MatrixLine<'a, T>
+where
+  T: StableLayout,
+{
+  #[inline]
+  fn clone(&self) -> Self {
+    Self { iter: self.iter.clone() }
+  }
+}
+
+impl<'a, T> Iterator for MatrixLine<'a, T>
+where
+  T: StableLayout,
+{
+  type Item = &'a T;
+  #[inline]
+  fn next(&mut self) -> Option<&'a T> {
+    self.iter.next()
+  }
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
+
+impl<'a, T> ExactSizeIterator for MatrixLine<'a, T> where T: StableLayout {}
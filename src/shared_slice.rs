@@ -1,4 +1,6 @@
-use core::{fmt::Debug, marker::PhantomData, ops::Deref, slice};
+use core::{
+  borrow::Borrow, fmt::Debug, iter::Map, marker::PhantomData, ops::Deref, slice,
+};
 
 use super::StableLayout;
 
@@ -56,6 +58,47 @@ where
 
 unsafe impl<'a, T: StableLayout> StableLayout for SharedSlice<'a, T> {}
 
+/// The iterator returned by [`SharedSlice::windows`].
+pub type SharedWindows<'a, T> =
+  Map<slice::Windows<'a, T>, fn(&'a [T]) -> SharedSlice<'a, T>>;
+
+impl<'a, T> SharedSlice<'a, T>
+where
+  T: StableLayout,
+{
+  /// Iterates over all the overlapping windows of length `size`, as stable
+  /// views.
+  ///
+  /// This is the same as `<[T]>::windows`, except that each window is itself a
+  /// `SharedSlice`, so it can be handed straight to FFI code.
+  ///
+  /// ## Panics
+  /// If `size` is 0.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let data = [1, 2, 3, 4];
+  /// let shared = SharedSlice::from(&data[..]);
+  /// let sums: Vec<i32> = shared.windows(3).map(|w| w.iter().sum()).collect();
+  /// assert_eq!(sums, vec![6, 9]);
+  /// ```
+  #[inline]
+  pub fn windows(&self, size: usize) -> SharedWindows<'a, T> {
+    let sli: &'a [T] = (*self).into();
+    sli.windows(size).map(SharedSlice::from)
+  }
+
+  /// Like [`windows`](Self::windows), but gives `None` if `size` is 0.
+  #[inline]
+  pub fn try_windows(&self, size: usize) -> Option<SharedWindows<'a, T>> {
+    if size == 0 {
+      return None;
+    }
+    let sli: &'a [T] = (*self).into();
+    Some(sli.windows(size).map(SharedSlice::from))
+  }
+}
+
 impl<'a, T: Debug> Debug for SharedSlice<'a, T>
 where
   T: StableLayout,
@@ -113,12 +156,46 @@ where
   }
 }
 
+impl<'a, T> AsRef<[T]> for SharedSlice<'a, T>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn as_ref(&self) -> &[T] {
+    self
+  }
+}
+
+impl<'a, T> Borrow<[T]> for SharedSlice<'a, T>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn borrow(&self) -> &[T] {
+    self
+  }
+}
+
+impl<'b, 'a, T> IntoIterator for &'b SharedSlice<'a, T>
+where
+  T: StableLayout,
+{
+  type Item = &'b T;
+  type IntoIter = slice::Iter<'b, T>;
+  #[inline(always)]
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
 impl<'a, T> From<&'a [T]> for SharedSlice<'a, T>
 where
   T: StableLayout,
 {
   #[inline(always)]
   fn from(sli: &'a [T]) -> Self {
+    trace_conversion!(&'a [T] => Self, sli.as_ptr(), sli.len());
+    count_conversion!(SharedSlice, Into, core::mem::size_of_val(sli));
     let life = PhantomData;
     let len = sli.len();
     let ptr = sli.as_ptr();
@@ -132,7 +209,323 @@ where
 {
   #[inline(always)]
   fn from(shared: SharedSlice<'a, T>) -> Self {
+    trace_conversion!(SharedSlice<'a, T> => Self, shared.ptr, shared.len);
+    count_conversion!(
+      SharedSlice,
+      OutOf,
+      shared.len * core::mem::size_of::<T>()
+    );
     // Safety: See note at the top of the module.
     unsafe { slice::from_raw_parts(shared.ptr, shared.len) }
   }
 }
+
+#[cfg(feature = "bytemuck")]
+impl<'a, T> SharedSlice<'a, T>
+where
+  T: StableLayout + bytemuck::Pod,
+{
+  /// Views the slice as a slice of some other `Pod` type.
+  ///
+  /// This fails if the output alignment isn't met, or if the total size of
+  /// the slice isn't a multiple of the output element size.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let data = [0x0201_u16, 0x0403];
+  /// let shared = SharedSlice::from(&data[..]);
+  /// let bytes: SharedSlice<u8> = shared.try_cast().unwrap();
+  /// assert_eq!(bytes.len(), 4);
+  /// ```
+  #[inline]
+  pub fn try_cast<U>(self) -> Result<SharedSlice<'a, U>, bytemuck::PodCastError>
+  where
+    U: StableLayout + bytemuck::Pod,
+  {
+    let sli: &'a [T] = self.into();
+    bytemuck::try_cast_slice(sli).map(SharedSlice::from)
+  }
+}
+
+#[cfg(feature = "subtle")]
+impl<'a, T> subtle::ConstantTimeEq for SharedSlice<'a, T>
+where
+  T: StableLayout + subtle::ConstantTimeEq,
+{
+  /// Compares the elements in constant time (for a given length).
+  ///
+  /// Slices of different lengths are unequal, and that part of the check is
+  /// _not_ constant time.
+  #[inline]
+  fn ct_eq(&self, other: &Self) -> subtle::Choice {
+    self.deref().ct_eq(other.deref())
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'a, T> serde::Serialize for SharedSlice<'a, T>
+where
+  T: StableLayout + serde::Serialize,
+{
+  /// Serializes as a sequence, the same as a slice.
+  #[inline]
+  fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+    self.deref().serialize(s)
+  }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for SharedSlice<'a, u8> {
+  /// Borrows some of the fuzzer's input bytes.
+  #[inline]
+  fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+    <&'a [u8]>::arbitrary(u).map(Self::from)
+  }
+  #[inline]
+  fn arbitrary_take_rest(
+    u: arbitrary::Unstructured<'a>,
+  ) -> arbitrary::Result<Self> {
+    <&'a [u8]>::arbitrary_take_rest(u).map(Self::from)
+  }
+  #[inline]
+  fn size_hint(depth: usize) -> (usize, Option<usize>) {
+    <&'a [u8]>::size_hint(depth)
+  }
+}
+
+#[cfg(feature = "heapless")]
+impl<'a, T, const N: usize> From<&'a heapless::Vec<T, N>> for SharedSlice<'a, T>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn from(v: &'a heapless::Vec<T, N>) -> Self {
+    Self::from(v.as_slice())
+  }
+}
+
+#[cfg(feature = "heapless")]
+impl<'a, T, const N: usize> core::convert::TryFrom<SharedSlice<'a, T>>
+  for heapless::Vec<T, N>
+where
+  T: StableLayout + Clone,
+{
+  type Error = heapless::CapacityError;
+  /// Clones the elements into a new `heapless::Vec`, failing if there are
+  /// more than `N` of them.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// # use core::convert::TryFrom;
+  /// let mut v: heapless::Vec<u8, 4> = heapless::Vec::new();
+  /// v.extend_from_slice(&[1, 2, 3]).unwrap();
+  /// let shared = SharedSlice::from(&v);
+  /// assert_eq!(&heapless::Vec::<u8, 3>::try_from(shared).unwrap(), &v);
+  /// assert!(heapless::Vec::<u8, 2>::try_from(shared).is_err());
+  /// ```
+  #[inline]
+  fn try_from(
+    shared: SharedSlice<'a, T>,
+  ) -> Result<Self, heapless::CapacityError> {
+    let sli: &'a [T] = shared.into();
+    Self::try_from(sli)
+  }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<'a, T, const N: usize> From<&'a arrayvec::ArrayVec<T, N>>
+  for SharedSlice<'a, T>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn from(v: &'a arrayvec::ArrayVec<T, N>) -> Self {
+    Self::from(v.as_slice())
+  }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<'a, T, const N: usize> core::convert::TryFrom<SharedSlice<'a, T>>
+  for arrayvec::ArrayVec<T, N>
+where
+  T: StableLayout + Clone,
+{
+  type Error = arrayvec::CapacityError;
+  /// Clones the elements into a new `ArrayVec`, failing if there are more
+  /// than `N` of them.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// # use core::convert::TryFrom;
+  /// use arrayvec::ArrayVec;
+  /// let v: ArrayVec<u8, 4> = [1, 2, 3].iter().copied().collect();
+  /// let shared = SharedSlice::from(&v);
+  /// let back = ArrayVec::<u8, 3>::try_from(shared).unwrap();
+  /// assert_eq!(&back[..], &v[..]);
+  /// assert!(ArrayVec::<u8, 2>::try_from(shared).is_err());
+  /// ```
+  #[inline]
+  fn try_from(
+    shared: SharedSlice<'a, T>,
+  ) -> Result<Self, arrayvec::CapacityError> {
+    let sli: &'a [T] = shared.into();
+    Self::try_from(sli)
+  }
+}
+
+#[cfg(feature = "smallvec")]
+impl<'a, A> From<&'a smallvec::SmallVec<A>> for SharedSlice<'a, A::Item>
+where
+  A: smallvec::Array,
+  A::Item: StableLayout,
+{
+  /// Borrows the elements, wherever they're stored.
+  ///
+  /// If the `SmallVec` is storing its elements inline then the view points
+  /// into the `SmallVec` itself, so it's invalidated if the `SmallVec` moves.
+  /// The borrow checker already prevents that on the Rust side, but C code
+  /// must not hold on to the pointer.
+  #[inline(always)]
+  fn from(v: &'a smallvec::SmallVec<A>) -> Self {
+    Self::from(v.as_slice())
+  }
+}
+
+#[cfg(feature = "tinyvec")]
+impl<'a, A> From<&'a tinyvec::ArrayVec<A>> for SharedSlice<'a, A::Item>
+where
+  A: tinyvec::Array,
+  A::Item: StableLayout,
+{
+  #[inline(always)]
+  fn from(v: &'a tinyvec::ArrayVec<A>) -> Self {
+    Self::from(v.as_slice())
+  }
+}
+
+#[cfg(all(feature = "tinyvec", feature = "unsafe_alloc"))]
+impl<'a, A> From<&'a tinyvec::TinyVec<A>> for SharedSlice<'a, A::Item>
+where
+  A: tinyvec::Array,
+  A::Item: StableLayout,
+{
+  #[inline(always)]
+  fn from(v: &'a tinyvec::TinyVec<A>) -> Self {
+    Self::from(v.as_slice())
+  }
+}
+
+#[cfg(feature = "widestring")]
+impl<'a> From<&'a widestring::U16Str> for SharedSlice<'a, u16> {
+  #[inline(always)]
+  fn from(s: &'a widestring::U16Str) -> Self {
+    Self::from(s.as_slice())
+  }
+}
+
+#[cfg(feature = "widestring")]
+impl<'a> From<&'a widestring::U16CStr> for SharedSlice<'a, u16> {
+  /// The slice includes the nul terminator, so C code can also use the
+  /// pointer as a plain `const wchar_t *` string.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// # use core::convert::TryFrom;
+  /// use widestring::{u16cstr, U16CStr};
+  /// let wide = u16cstr!("hi");
+  /// let shared = SharedSlice::from(wide);
+  /// assert_eq!(&shared[..], &[u16::from(b'h'), u16::from(b'i'), 0]);
+  /// assert_eq!(<&U16CStr>::try_from(shared).unwrap(), wide);
+  /// ```
+  #[inline(always)]
+  fn from(s: &'a widestring::U16CStr) -> Self {
+    Self::from(s.as_slice_with_nul())
+  }
+}
+
+#[cfg(feature = "widestring")]
+impl<'a> From<SharedSlice<'a, u16>> for &'a widestring::U16Str {
+  #[inline(always)]
+  fn from(shared: SharedSlice<'a, u16>) -> Self {
+    let sli: &'a [u16] = shared.into();
+    widestring::U16Str::from_slice(sli)
+  }
+}
+
+#[cfg(feature = "widestring")]
+impl<'a> core::convert::TryFrom<SharedSlice<'a, u16>>
+  for &'a widestring::U16CStr
+{
+  type Error = widestring::error::NulError<u16>;
+  /// Fails unless the last element is the only nul.
+  #[inline]
+  fn try_from(
+    shared: SharedSlice<'a, u16>,
+  ) -> Result<Self, widestring::error::NulError<u16>> {
+    let sli: &'a [u16] = shared.into();
+    widestring::U16CStr::from_slice(sli)
+  }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, T> rayon::iter::IntoParallelIterator for SharedSlice<'a, T>
+where
+  T: StableLayout + Sync,
+{
+  type Item = &'a T;
+  type Iter = rayon::slice::Iter<'a, T>;
+  /// Iterates over the elements in parallel.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// use rayon::prelude::*;
+  /// let data: Vec<u32> = (1..=100).collect();
+  /// let shared = SharedSlice::from(&data[..]);
+  /// assert_eq!(shared.par_iter().sum::<u32>(), 5050);
+  /// assert_eq!(shared.into_par_iter().max(), Some(&100));
+  /// assert_eq!(shared.par_chunks(10).count(), 10);
+  /// ```
+  #[inline(always)]
+  fn into_par_iter(self) -> Self::Iter {
+    let sli: &'a [T] = self.into();
+    sli.into_par_iter()
+  }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, 'b, T> rayon::iter::IntoParallelIterator for &'b SharedSlice<'a, T>
+where
+  T: StableLayout + Sync,
+{
+  type Item = &'b T;
+  type Iter = rayon::slice::Iter<'b, T>;
+  #[inline(always)]
+  fn into_par_iter(self) -> Self::Iter {
+    self.deref().into_par_iter()
+  }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, T> rayon::slice::ParallelSlice<T> for SharedSlice<'a, T>
+where
+  T: StableLayout + Sync,
+{
+  #[inline(always)]
+  fn as_parallel_slice(&self) -> &[T] {
+    self
+  }
+}
+
+// Safety: The slice is `'static`, so the memory can't be freed during the
+// transfer, and the pointer and length never change.
+#[cfg(feature = "embedded-dma")]
+unsafe impl<T> embedded_dma::ReadBuffer for SharedSlice<'static, T>
+where
+  T: StableLayout + embedded_dma::Word,
+{
+  type Word = T;
+  #[inline(always)]
+  unsafe fn read_buffer(&self) -> (*const T, usize) {
+    (self.ptr, self.len)
+  }
+}
@@ -1,4 +1,12 @@
-use core::{fmt::Debug, marker::PhantomData, ops::Deref, slice, str};
+use core::{
+  borrow::Borrow,
+  fmt::{Debug, Display},
+  marker::PhantomData,
+  ops::Deref,
+  slice,
+  slice::SliceIndex,
+  str,
+};
 
 use super::StableLayout;
 
@@ -47,6 +55,96 @@ pub struct SharedStr<'a> {
 
 unsafe impl<'a> StableLayout for SharedStr<'a> {}
 
+impl<'a> SharedStr<'a> {
+  /// Checks that some bytes are valid UTF-8 and then views them as a string.
+  #[inline]
+  pub fn from_utf8(bytes: &'a [u8]) -> Result<Self, str::Utf8Error> {
+    str::from_utf8(bytes).map(Self::from)
+  }
+
+  /// Gets a substring, or `None` if the range is out of bounds or doesn't
+  /// fall on `char` boundaries.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let shared = SharedStr::from("héllo");
+  /// assert_eq!(&*shared.get(3..).unwrap(), "llo");
+  /// assert!(shared.get(2..).is_none()); // inside of the 'é'
+  /// assert!(shared.get(..10).is_none());
+  /// ```
+  #[inline]
+  pub fn get<I>(&self, index: I) -> Option<Self>
+  where
+    I: SliceIndex<str, Output = str>,
+  {
+    let s: &'a str = (*self).into();
+    s.get(index).map(Self::from)
+  }
+
+  /// The largest `char` boundary that's at most `index`.
+  ///
+  /// An `index` past the end of the string gives the string's length. Use this
+  /// to cut a string down to a byte budget without splitting a `char`.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let shared = SharedStr::from("héllo");
+  /// assert_eq!(shared.floor_char_boundary(2), 1);
+  /// assert_eq!(shared.floor_char_boundary(3), 3);
+  /// assert_eq!(shared.floor_char_boundary(99), 6);
+  /// ```
+  #[inline]
+  pub fn floor_char_boundary(&self, index: usize) -> usize {
+    if index >= self.len {
+      self.len
+    } else {
+      (0..=index).rev().find(|&i| self.is_char_boundary(i)).unwrap_or(0)
+    }
+  }
+
+  /// Splits the string at the first `delimiter`, leaving the delimiter out of
+  /// both halves.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let shared = SharedStr::from("key=value=more");
+  /// let (k, v) = shared.split_once('=').unwrap();
+  /// assert_eq!((&*k, &*v), ("key", "value=more"));
+  /// assert!(shared.split_once('#').is_none());
+  /// ```
+  #[inline]
+  pub fn split_once(&self, delimiter: char) -> Option<(Self, Self)> {
+    let s: &'a str = (*self).into();
+    s.split_once(delimiter).map(|(a, b)| (Self::from(a), Self::from(b)))
+  }
+
+  /// Splits some bytes into string pieces, with each invalid UTF-8 sequence
+  /// replaced by U+FFFD (`�`).
+  ///
+  /// This is the same replacement that `String::from_utf8_lossy` does, but it
+  /// doesn't allocate: the valid pieces borrow from `bytes`, and each
+  /// replacement character is its own `'static` piece. You can push the pieces
+  /// into a `StableRope`, or write them out one at a time.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let bytes = b"ab\xFFcd";
+  /// let mut out = String::new();
+  /// for piece in SharedStr::lossy_pieces(bytes) {
+  ///   out.push_str(&piece);
+  /// }
+  /// assert_eq!(out, "ab\u{FFFD}cd");
+  /// ```
+  #[inline]
+  pub fn lossy_pieces(bytes: &'a [u8]) -> impl Iterator<Item = Self> + 'a {
+    bytes.utf8_chunks().flat_map(|chunk| {
+      let valid = Some(chunk.valid()).filter(|s| !s.is_empty());
+      let replacement = (!chunk.invalid().is_empty()).then_some("\u{FFFD}");
+      valid.into_iter().chain(replacement).map(Self::from)
+    })
+  }
+}
+
 impl<'a> Debug for SharedStr<'a> {
   /// Debug prints as a slice would.
   fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
@@ -54,6 +152,13 @@ impl<'a> Debug for SharedStr<'a> {
   }
 }
 
+impl<'a> Display for SharedStr<'a> {
+  /// Display prints as a `str` would.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    Display::fmt(self.deref(), f)
+  }
+}
+
 impl<'a> Clone for SharedStr<'a> {
   #[inline(always)]
   fn clone(&self) -> Self {
@@ -91,9 +196,32 @@ impl<'a> Deref for SharedStr<'a> {
   }
 }
 
+impl<'a> AsRef<str> for SharedStr<'a> {
+  #[inline(always)]
+  fn as_ref(&self) -> &str {
+    self
+  }
+}
+
+impl<'a> AsRef<[u8]> for SharedStr<'a> {
+  #[inline(always)]
+  fn as_ref(&self) -> &[u8] {
+    self.as_bytes()
+  }
+}
+
+impl<'a> Borrow<str> for SharedStr<'a> {
+  #[inline(always)]
+  fn borrow(&self) -> &str {
+    self
+  }
+}
+
 impl<'a> From<&'a str> for SharedStr<'a> {
   #[inline(always)]
   fn from(s: &'a str) -> Self {
+    trace_conversion!(&'a str => Self, s.as_ptr(), s.len());
+    count_conversion!(SharedStr, Into, s.len());
     let life = PhantomData;
     let len = s.len();
     let ptr = s.as_ptr();
@@ -104,9 +232,61 @@ impl<'a> From<&'a str> for SharedStr<'a> {
 impl<'a> From<SharedStr<'a>> for &'a str {
   #[inline(always)]
   fn from(shared: SharedStr<'a>) -> Self {
+    trace_conversion!(SharedStr<'a> => Self, shared.ptr, shared.len);
+    count_conversion!(SharedStr, OutOf, shared.len);
     // Safety: See note at the top of the module.
     unsafe {
       str::from_utf8_unchecked(slice::from_raw_parts(shared.ptr, shared.len))
     }
   }
 }
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for SharedStr<'a> {
+  /// Serializes as a string.
+  #[inline]
+  fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(self)
+  }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for SharedStr<'a> {
+  /// Borrows the longest valid UTF-8 prefix of some of the fuzzer's input.
+  #[inline]
+  fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+    <&'a str>::arbitrary(u).map(Self::from)
+  }
+  #[inline]
+  fn arbitrary_take_rest(
+    u: arbitrary::Unstructured<'a>,
+  ) -> arbitrary::Result<Self> {
+    <&'a str>::arbitrary_take_rest(u).map(Self::from)
+  }
+  #[inline]
+  fn size_hint(depth: usize) -> (usize, Option<usize>) {
+    <&'a str>::size_hint(depth)
+  }
+}
+
+#[cfg(feature = "heapless")]
+impl<'a, const N: usize> From<&'a heapless::String<N>> for SharedStr<'a> {
+  #[inline(always)]
+  fn from(s: &'a heapless::String<N>) -> Self {
+    Self::from(s.as_str())
+  }
+}
+
+#[cfg(feature = "heapless")]
+impl<'a, const N: usize> core::convert::TryFrom<SharedStr<'a>>
+  for heapless::String<N>
+{
+  type Error = heapless::CapacityError;
+  /// Copies the string into a new `heapless::String`, failing if it's longer
+  /// than `N` bytes.
+  #[inline]
+  fn try_from(shared: SharedStr<'a>) -> Result<Self, heapless::CapacityError> {
+    let s: &'a str = shared.into();
+    Self::try_from(s)
+  }
+}
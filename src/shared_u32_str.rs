@@ -0,0 +1,169 @@
+use core::{
+  fmt::{Debug, Display, Write},
+  iter::Copied,
+  marker::PhantomData,
+  ops::Deref,
+  slice,
+};
+
+use super::StableLayout;
+
+// General Safety Note: The soundness of the `SharedU32Str` type is centered
+// around the fact that the fields are all private, and so *safe rust* must
+// construct values of the type from an existing valid slice. However, because
+// the type is `repr(C)` it can of course be constructed with unsafe rust, or
+// even by foreign code. It is the responsibility of _the other code_ to ensure
+// that the actual fields are valid for being turned into a slice.
+
+/// A struct for **shared** UTF-32 string views with a stable layout.
+///
+/// This is a `repr(C)` variant of `&[char]`. Each element is a `u32` holding a
+/// Unicode scalar value, which is the string format that some GUI toolkits and
+/// scripting runtimes use.
+///
+/// ## Unsafety
+///
+/// Because this type is primarily intended to help _unsafe_ Rust we should
+/// discuss the precise guarantees offered:
+/// * **Validity Invariants**
+///   * The data layout is a `*const char` and then a `usize`.
+/// * **Soundness Invariants**
+///   * The `*const char` must point to the start of a valid `&[char]`.
+///   * The `usize` must be the correct length of that valid `&[char]`.
+///   * For as long as the `SharedU32Str` exists the memory in question has a
+///     shared borrow over it (tracked via `PhantomData`).
+///   * Every element must be a Unicode scalar value (`0..=0x10FFFF`, excluding
+///     the surrogates `0xD800..=0xDFFF`).
+///
+/// This type matches up with the following C layout:
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `SharedU32Str<'a>`
+/// typedef struct {
+///   uint32_t const *ptr;
+///   uintptr_t len;
+/// } SharedU32Str;
+/// ```
+#[repr(C)]
+pub struct SharedU32Str<'a> {
+  ptr: *const char,
+  len: usize,
+  life: PhantomData<&'a [char]>,
+}
+
+unsafe impl<'a> StableLayout for SharedU32Str<'a> {}
+
+impl<'a> SharedU32Str<'a> {
+  /// Checks that every `u32` is a Unicode scalar value and then views them as a
+  /// string.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let data = [0x68, 0x69, 0x1F600];
+  /// let s = SharedU32Str::from_u32s(&data).unwrap();
+  /// assert!(s.chars().eq("hi\u{1F600}".chars()));
+  /// assert!(SharedU32Str::from_u32s(&[0xD800]).is_none());
+  /// ```
+  #[inline]
+  pub fn from_u32s(data: &'a [u32]) -> Option<Self> {
+    if data.iter().all(|&u| char::from_u32(u).is_some()) {
+      // Safety: `char` and `u32` have the same size and alignment, and we just
+      // checked that every element is a valid `char`.
+      let chars = unsafe {
+        slice::from_raw_parts(data.as_ptr() as *const char, data.len())
+      };
+      Some(Self::from(chars))
+    } else {
+      None
+    }
+  }
+
+  /// Iterates over the characters of the string.
+  #[inline(always)]
+  pub fn chars(&self) -> Copied<slice::Iter<'a, char>> {
+    let chars: &'a [char] = (*self).into();
+    chars.iter().copied()
+  }
+
+  /// Views the string as raw `u32` values.
+  #[inline(always)]
+  pub fn as_u32s(&self) -> &'a [u32] {
+    // Safety: See note at the top of the module. Every `char` is a valid `u32`.
+    unsafe { slice::from_raw_parts(self.ptr as *const u32, self.len) }
+  }
+}
+
+impl<'a> Debug for SharedU32Str<'a> {
+  /// Debug prints as a `str` would.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.write_char('"')?;
+    for c in self.chars() {
+      for e in c.escape_debug() {
+        f.write_char(e)?;
+      }
+    }
+    f.write_char('"')
+  }
+}
+
+impl<'a> Display for SharedU32Str<'a> {
+  /// Display prints as a `str` would.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    self.chars().try_for_each(|c| f.write_char(c))
+  }
+}
+
+impl<'a> Clone for SharedU32Str<'a> {
+  #[inline(always)]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<'a> Copy for SharedU32Str<'a> {}
+
+impl<'a> Default for SharedU32Str<'a> {
+  /// Defaults to an empty string.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let shared: SharedU32Str<'static> = SharedU32Str::default();
+  /// assert_eq!(shared.len(), 0);
+  /// ```
+  #[inline(always)]
+  fn default() -> Self {
+    let life = PhantomData;
+    let len = 0;
+    let ptr = core::ptr::NonNull::dangling().as_ptr();
+    Self { ptr, len, life }
+  }
+}
+
+impl<'a> Deref for SharedU32Str<'a> {
+  type Target = [char];
+  #[inline(always)]
+  fn deref(&self) -> &[char] {
+    // Safety: See note at the top of the module.
+    unsafe { slice::from_raw_parts(self.ptr, self.len) }
+  }
+}
+
+impl<'a> From<&'a [char]> for SharedU32Str<'a> {
+  #[inline(always)]
+  fn from(c: &'a [char]) -> Self {
+    trace_conversion!(&'a [char] => Self, c.as_ptr(), c.len());
+    let life = PhantomData;
+    let len = c.len();
+    let ptr = c.as_ptr();
+    Self { ptr, len, life }
+  }
+}
+
+impl<'a> From<SharedU32Str<'a>> for &'a [char] {
+  #[inline(always)]
+  fn from(shared: SharedU32Str<'a>) -> Self {
+    trace_conversion!(SharedU32Str<'a> => Self, shared.ptr, shared.len);
+    // Safety: See note at the top of the module.
+    unsafe { slice::from_raw_parts(shared.ptr, shared.len) }
+  }
+}
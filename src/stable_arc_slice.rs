@@ -0,0 +1,231 @@
+#![cfg(all(feature = "unsafe_alloc", target_has_atomic = "ptr"))]
+
+use super::{
+  SharedSlice, SharedStr, StableLayout, StableRefCounted, StableString,
+  StableVec,
+};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{fmt::Debug, ops::Deref, ptr::NonNull, str};
+
+/// The reference counted object behind a [`StableArcSlice`].
+pub type FrozenVec<T> = StableRefCounted<StableVec<T>>;
+
+/// A reference counted, immutable slice with a stable layout.
+///
+/// This is a pointer to a [`StableRefCounted`] that holds a [`StableVec`].
+/// Make one with [`StableVec::freeze`], and then clone it to share the data
+/// with as many FFI consumers as you like, for the cost of a count increment.
+/// C code keeps the data alive with `StableRefCounted::acquire` and
+/// `StableRefCounted::release` on the pointer, and the vec is freed when the
+/// count reaches 0.
+///
+/// ```c
+/// // Identical layout to `StableArcSlice<T>`
+/// typedef struct FrozenVec_T *StableArcSlice_T;
+/// ```
+#[repr(transparent)]
+pub struct StableArcSlice<T>
+where
+  T: StableLayout,
+{
+  rc: NonNull<FrozenVec<T>>,
+}
+
+unsafe impl<T: StableLayout> StableLayout for StableArcSlice<T> {}
+
+// Safety: The same as `Arc<[T]>`.
+unsafe impl<T: StableLayout + Send + Sync> Send for StableArcSlice<T> {}
+unsafe impl<T: StableLayout + Send + Sync> Sync for StableArcSlice<T> {}
+
+impl<T> StableVec<T>
+where
+  T: StableLayout,
+{
+  /// Moves the vec into a reference counted, immutable slice.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let frozen = StableVec::from(vec![1, 2, 3]).freeze();
+  /// let other = frozen.clone();
+  /// assert_eq!(frozen.ref_count(), 2);
+  /// assert_eq!(&other[..], &[1, 2, 3]);
+  /// ```
+  #[inline]
+  pub fn freeze(self) -> StableArcSlice<T> {
+    unsafe extern "C" fn drop_frozen<T: StableLayout>(this: *mut FrozenVec<T>) {
+      let b = Box::from_raw(this);
+      drop(Vec::from(core::ptr::read(b.payload())));
+    }
+    let b = Box::new(StableRefCounted::with_drop_fn(self, drop_frozen::<T>));
+    // Safety: `Box::into_raw` is never null.
+    StableArcSlice { rc: unsafe { NonNull::new_unchecked(Box::into_raw(b)) } }
+  }
+}
+
+impl<T> StableArcSlice<T>
+where
+  T: StableLayout,
+{
+  /// The number of references to the data.
+  ///
+  /// Other threads can change the count at any time, so this is only a
+  /// snapshot.
+  #[inline(always)]
+  pub fn ref_count(&self) -> usize {
+    self.object().count()
+  }
+
+  /// Views the data.
+  #[inline(always)]
+  pub fn as_shared(&self) -> SharedSlice<'_, T> {
+    SharedSlice::from(self.deref())
+  }
+
+  /// Gives up this reference as a raw pointer, for passing to C.
+  #[inline(always)]
+  pub fn into_raw(self) -> NonNull<FrozenVec<T>> {
+    let rc = self.rc;
+    core::mem::forget(self);
+    rc
+  }
+
+  /// Takes back a reference given up with [`into_raw`](Self::into_raw).
+  ///
+  /// ## Safety
+  /// * `rc` must have come from `into_raw` (or from a C side `acquire` on such
+  ///   a pointer), and the reference it represents is taken over by the new
+  ///   value.
+  #[inline(always)]
+  pub unsafe fn from_raw(rc: NonNull<FrozenVec<T>>) -> Self {
+    Self { rc }
+  }
+
+  #[inline(always)]
+  fn object(&self) -> &FrozenVec<T> {
+    // Safety: We hold a reference, so the object is alive.
+    unsafe { self.rc.as_ref() }
+  }
+}
+
+impl<T> Clone for StableArcSlice<T>
+where
+  T: StableLayout,
+{
+  #[inline]
+  fn clone(&self) -> Self {
+    // Safety: We hold a reference, so the object is alive.
+    unsafe { StableRefCounted::acquire(self.rc.as_ptr()) };
+    Self { rc: self.rc }
+  }
+}
+
+impl<T> Drop for StableArcSlice<T>
+where
+  T: StableLayout,
+{
+  #[inline]
+  fn drop(&mut self) {
+    // Safety: We hold a reference, and give it up here.
+    unsafe { StableRefCounted::release(self.rc.as_ptr()) }
+  }
+}
+
+impl<T> Deref for StableArcSlice<T>
+where
+  T: StableLayout,
+{
+  type Target = [T];
+  #[inline(always)]
+  fn deref(&self) -> &[T] {
+    self.object().payload()
+  }
+}
+
+impl<T: Debug> Debug for StableArcSlice<T>
+where
+  T: StableLayout,
+{
+  /// Debug prints as a slice would.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    Debug::fmt(self.deref(), f)
+  }
+}
+
+impl<'a, T: Clone> From<SharedSlice<'a, T>> for StableArcSlice<T>
+where
+  T: StableLayout,
+{
+  /// Clones the data into a new frozen slice.
+  #[inline]
+  fn from(shared: SharedSlice<'a, T>) -> Self {
+    StableVec::from(Vec::from(&shared[..])).freeze()
+  }
+}
+
+/// A reference counted, immutable string with a stable layout.
+///
+/// This is a [`StableArcSlice<u8>`](StableArcSlice) that always holds UTF-8.
+/// Make one with [`StableString::freeze`].
+///
+/// ```rust
+/// # use chromium::*;
+/// let frozen = StableString::from(String::from("hello")).freeze();
+/// let shared: SharedStr<'_> = frozen.as_shared();
+/// assert_eq!(&*shared, "hello");
+/// let copied = StableArcStr::from(SharedStr::from("hi"));
+/// assert_eq!(&*copied, "hi");
+/// ```
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct StableArcStr {
+  bytes: StableArcSlice<u8>,
+}
+
+unsafe impl StableLayout for StableArcStr {}
+
+impl StableString {
+  /// Moves the string into a reference counted, immutable string.
+  #[inline]
+  pub fn freeze(self) -> StableArcStr {
+    let bytes = String::from(self).into_bytes();
+    StableArcStr { bytes: StableVec::from(bytes).freeze() }
+  }
+}
+
+impl StableArcStr {
+  /// The number of references to the data.
+  #[inline(always)]
+  pub fn ref_count(&self) -> usize {
+    self.bytes.ref_count()
+  }
+
+  /// Views the string.
+  #[inline(always)]
+  pub fn as_shared(&self) -> SharedStr<'_> {
+    SharedStr::from(self.deref())
+  }
+}
+
+impl Deref for StableArcStr {
+  type Target = str;
+  #[inline(always)]
+  fn deref(&self) -> &str {
+    // Safety: The bytes always came from a `String`.
+    unsafe { str::from_utf8_unchecked(&self.bytes) }
+  }
+}
+
+impl Debug for StableArcStr {
+  /// Debug prints as a `str` would.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    Debug::fmt(self.deref(), f)
+  }
+}
+
+impl<'a> From<SharedStr<'a>> for StableArcStr {
+  /// Copies the string into a new frozen string.
+  #[inline]
+  fn from(shared: SharedStr<'a>) -> Self {
+    StableString::from(String::from(&*shared)).freeze()
+  }
+}
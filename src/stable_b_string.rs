@@ -0,0 +1,187 @@
+#![cfg(feature = "unsafe_alloc")]
+
+use super::{SharedBStr, StableLayout, StableString, StableVec};
+use alloc::{string::String, vec::Vec};
+use core::{
+  fmt::{Debug, Display},
+  ops::{Deref, DerefMut},
+};
+
+/// An owned byte string with a stable layout.
+///
+/// This is a [`StableVec<u8>`](StableVec) that's conventionally UTF-8 text,
+/// the owned partner of [`SharedBStr`]. Like any `StableVec` it leaks its
+/// memory if you drop it without turning it back into a Rust type.
+///
+/// ## Unsafety
+///
+/// This has all the same invariants as `StableVec<u8>`.
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StableBString`
+/// typedef struct {
+///   uint8_t *ptr;
+///   uintptr_t len;
+///   uintptr_t cap;
+/// } StableBString;
+/// ```
+#[repr(transparent)]
+pub struct StableBString {
+  bytes: StableVec<u8>,
+}
+
+unsafe impl StableLayout for StableBString {}
+
+impl StableBString {
+  /// Borrows the string as a shared view.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let b = StableBString::from(b"log \xFF line".to_vec());
+  /// assert_eq!(format!("{}", b.as_shared()), "log \u{FFFD} line");
+  /// let s = b.into_string_lossy();
+  /// assert_eq!(s, "log \u{FFFD} line");
+  /// ```
+  #[inline(always)]
+  pub fn as_shared(&self) -> SharedBStr<'_> {
+    SharedBStr::from(&self.bytes[..])
+  }
+
+  /// Turns the bytes into a `String`, if they're valid UTF-8.
+  ///
+  /// The error holds the bytes, so they aren't leaked.
+  #[inline]
+  #[track_caller]
+  pub fn into_string(self) -> Result<String, alloc::string::FromUtf8Error> {
+    String::from_utf8(self.bytes.into())
+  }
+
+  /// Turns the bytes into a `String`, replacing each invalid UTF-8 sequence
+  /// with U+FFFD (`�`).
+  ///
+  /// Valid UTF-8 keeps the same allocation.
+  #[inline]
+  #[track_caller]
+  pub fn into_string_lossy(self) -> String {
+    match self.into_string() {
+      Ok(s) => s,
+      Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+    }
+  }
+}
+
+impl Deref for StableBString {
+  type Target = [u8];
+  #[inline(always)]
+  fn deref(&self) -> &[u8] {
+    &self.bytes
+  }
+}
+
+impl DerefMut for StableBString {
+  #[inline(always)]
+  fn deref_mut(&mut self) -> &mut [u8] {
+    &mut self.bytes
+  }
+}
+
+impl Debug for StableBString {
+  /// Debug prints as a `SharedBStr` would.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    Debug::fmt(&self.as_shared(), f)
+  }
+}
+
+impl Display for StableBString {
+  /// Display prints as a `SharedBStr` would.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    Display::fmt(&self.as_shared(), f)
+  }
+}
+
+impl Default for StableBString {
+  /// Defaults to an empty string.
+  #[inline(always)]
+  fn default() -> Self {
+    Self { bytes: StableVec::default() }
+  }
+}
+
+impl From<SharedBStr<'_>> for StableBString {
+  /// Copies the bytes into a new string.
+  #[inline]
+  fn from(shared: SharedBStr<'_>) -> Self {
+    Self::from(shared.to_vec())
+  }
+}
+
+impl From<Vec<u8>> for StableBString {
+  #[inline]
+  fn from(v: Vec<u8>) -> Self {
+    Self { bytes: StableVec::from(v) }
+  }
+}
+
+impl From<String> for StableBString {
+  #[inline]
+  fn from(s: String) -> Self {
+    Self::from(s.into_bytes())
+  }
+}
+
+impl From<StableVec<u8>> for StableBString {
+  #[inline(always)]
+  fn from(bytes: StableVec<u8>) -> Self {
+    Self { bytes }
+  }
+}
+
+impl From<StableString> for StableBString {
+  #[inline]
+  #[track_caller]
+  fn from(s: StableString) -> Self {
+    Self::from(String::from(s))
+  }
+}
+
+impl From<StableBString> for StableVec<u8> {
+  #[inline(always)]
+  fn from(s: StableBString) -> Self {
+    s.bytes
+  }
+}
+
+impl From<StableBString> for Vec<u8> {
+  #[inline]
+  #[track_caller]
+  fn from(s: StableBString) -> Self {
+    Vec::from(s.bytes)
+  }
+}
+
+#[cfg(feature = "bstr")]
+impl From<bstr::BString> for StableBString {
+  /// Takes ownership of the string's allocation.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// use bstr::BString;
+  /// let b = StableBString::from(BString::from(&b"a\xFFb"[..]));
+  /// assert_eq!(BString::from(b), BString::from(&b"a\xFFb"[..]));
+  /// ```
+  #[inline]
+  fn from(s: bstr::BString) -> Self {
+    Self::from(Vec::from(s))
+  }
+}
+
+#[cfg(feature = "bstr")]
+impl From<StableBString> for bstr::BString {
+  /// Takes back ownership of the allocation.
+  #[inline]
+  #[track_caller]
+  fn from(s: StableBString) -> Self {
+    bstr::BString::from(Vec::from(s))
+  }
+}
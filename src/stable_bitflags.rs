@@ -0,0 +1,53 @@
+#![cfg(feature = "bitflags")]
+
+/// Implements [`StableLayout`](crate::StableLayout) for types generated by
+/// [`bitflags!`](https://docs.rs/bitflags/2/bitflags/macro.bitflags.html).
+///
+/// The `bitflags!` macro makes a struct that wraps an integer, but it's only
+/// _guaranteed_ to have the same layout as that integer if you put
+/// `#[repr(transparent)]` on the struct within the `bitflags!` invocation. You
+/// must do that for every type you pass to this macro.
+///
+/// As a backstop, the macro checks at compile time that each type has the same
+/// size and alignment as its `Bits` type, and that the `Bits` type is itself
+/// `StableLayout`.
+///
+/// ```rust
+/// # use chromium::*;
+/// use bitflags::bitflags;
+///
+/// bitflags! {
+///   #[repr(transparent)]
+///   #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///   pub struct OpenFlags: u32 {
+///     const READ = 1 << 0;
+///     const WRITE = 1 << 1;
+///   }
+/// }
+/// impl_stable_layout_for_bitflags!(OpenFlags);
+///
+/// let flags = [OpenFlags::READ, OpenFlags::READ | OpenFlags::WRITE];
+/// let shared = SharedSlice::from(&flags[..]);
+/// assert_eq!(shared[1], OpenFlags::all());
+/// ```
+#[macro_export]
+macro_rules! impl_stable_layout_for_bitflags {
+  ( $( $t:ty ),* $(,)? ) => {
+    $(
+      const _: () = {
+        type Bits = <$t as $crate::__bitflags::Flags>::Bits;
+        fn bits_are_stable<B: $crate::StableLayout>() {}
+        let _ = bits_are_stable::<Bits>;
+        assert!(
+          ::core::mem::size_of::<$t>() == ::core::mem::size_of::<Bits>()
+        );
+        assert!(
+          ::core::mem::align_of::<$t>() == ::core::mem::align_of::<Bits>()
+        );
+      };
+      // Safety: The caller promises that the type was declared with
+      // `#[repr(transparent)]`, and we've checked the rest above.
+      unsafe impl $crate::StableLayout for $t {}
+    )*
+  };
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "bytemuck")]
+
+/// Implements [`StableLayout`](crate::StableLayout) for types that are
+/// [`bytemuck::Pod`](https://docs.rs/bytemuck/1/bytemuck/trait.Pod.html).
+///
+/// A `Pod` type must already be a primitive, or be `repr(C)` or
+/// `repr(transparent)` with no padding and only `Pod` fields, which is
+/// everything that `StableLayout` asks for. So if you already have a `Pod`
+/// type, this saves you from writing a second `unsafe impl` for it.
+///
+/// The macro checks at compile time that each type really is `Pod`. This
+/// can't be a blanket impl, since that would overlap the impls for types that
+/// aren't `Pod`.
+///
+/// ```rust
+/// # use chromium::*;
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// #[repr(C)]
+/// struct Vertex {
+///   pos: [f32; 2],
+///   color: u32,
+/// }
+/// unsafe impl bytemuck::Zeroable for Vertex {}
+/// unsafe impl bytemuck::Pod for Vertex {}
+/// impl_stable_layout_for_pod!(Vertex);
+///
+/// let verts = [Vertex { pos: [1.0, 2.0], color: 7 }];
+/// let shared = SharedSlice::from(&verts[..]);
+/// assert_eq!(shared[0].color, 7);
+/// ```
+#[macro_export]
+macro_rules! impl_stable_layout_for_pod {
+  ( $( $t:ty ),* $(,)? ) => {
+    $(
+      const _: () = {
+        fn is_pod<P: $crate::__bytemuck::Pod>() {}
+        let _ = is_pod::<$t>;
+      };
+      // Safety: `Pod` requires a primitive, `repr(C)`, or `repr(transparent)`
+      // layout, and we've checked that the type is `Pod` above.
+      unsafe impl $crate::StableLayout for $t {}
+    )*
+  };
+}
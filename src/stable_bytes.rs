@@ -0,0 +1,175 @@
+#![cfg(feature = "bytes")]
+
+use alloc::boxed::Box;
+use core::{ffi::c_void, fmt::Debug, ops::Deref, slice};
+
+use bytes::Bytes;
+
+use super::{SharedSlice, StableLayout};
+
+// General Safety Note: The soundness of the `StableBytes` type is centered
+// around the fact that the fields are all private, and so *safe rust* can only
+// make one from a `Bytes`. Values built by unsafe rust or by foreign code must
+// uphold the invariants listed on the type.
+
+/// An owned, immutable byte buffer with a stable layout.
+///
+/// This is a zero-copy handoff of a [`Bytes`]: a pointer and length for the
+/// data, plus an opaque `owner` pointer and a `release` function that gives up
+/// the owner's reference when called as `release(owner)`. Making one from a
+/// `Bytes` only boxes the `Bytes` handle itself, the data is never copied, and
+/// other clones of the same `Bytes` keep sharing it.
+///
+/// The C side calls `release(owner)` exactly once when it's done with the
+/// data. On the Rust side, dropping the value calls `release` for you, and
+/// turning it back into a `Bytes` is also zero-copy (the new `Bytes` calls
+/// `release` once its last clone is dropped).
+///
+/// ## Unsafety
+///
+/// Because this type is primarily intended to help _unsafe_ Rust we should
+/// discuss the precise guarantees offered:
+/// * **Validity Invariants**
+///   * The data layout is a `*const u8`, a `usize`, a `*mut c_void`, and then
+///     an `unsafe extern "C" fn(*mut c_void)`.
+/// * **Soundness Invariants**
+///   * The `*const u8` must point to the start of a valid `&[u8]`, and the
+///     `usize` must be the correct length of that slice.
+///   * The data must not change, and must stay valid, until `release` is
+///     called with `owner`.
+///   * `release` must be safe to call exactly once, with `owner`, from any
+///     thread.
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StableBytes`
+/// typedef struct {
+///   const uint8_t *ptr;
+///   uintptr_t len;
+///   void *owner;
+///   void (*release)(void *owner);
+/// } StableBytes;
+/// ```
+#[repr(C)]
+pub struct StableBytes {
+  ptr: *const u8,
+  len: usize,
+  owner: *mut c_void,
+  release: unsafe extern "C" fn(*mut c_void),
+}
+
+unsafe impl StableLayout for StableBytes {}
+
+// Safety: The data is immutable and `release` can be called from any thread.
+unsafe impl Send for StableBytes {}
+unsafe impl Sync for StableBytes {}
+
+impl StableBytes {
+  /// Makes a value from raw parts, such as a buffer that C code allocated.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// # use core::{ffi::c_void, sync::atomic::{AtomicUsize, Ordering}};
+  /// static RELEASED: AtomicUsize = AtomicUsize::new(0);
+  /// unsafe extern "C" fn release(_: *mut c_void) {
+  ///   RELEASED.fetch_add(1, Ordering::Relaxed);
+  /// }
+  /// static DATA: [u8; 3] = [1, 2, 3];
+  /// let buf = unsafe {
+  ///   StableBytes::from_raw_parts(
+  ///     DATA.as_ptr(),
+  ///     DATA.len(),
+  ///     core::ptr::null_mut(),
+  ///     release,
+  ///   )
+  /// };
+  /// let b = bytes::Bytes::from(buf);
+  /// let other = b.clone();
+  /// drop(b);
+  /// assert_eq!(&other[..], &[1, 2, 3]);
+  /// assert_eq!(RELEASED.load(Ordering::Relaxed), 0);
+  /// drop(other);
+  /// assert_eq!(RELEASED.load(Ordering::Relaxed), 1);
+  /// ```
+  ///
+  /// ## Safety
+  /// The parts must uphold the invariants listed on the type.
+  #[inline(always)]
+  pub const unsafe fn from_raw_parts(
+    ptr: *const u8, len: usize, owner: *mut c_void,
+    release: unsafe extern "C" fn(*mut c_void),
+  ) -> Self {
+    Self { ptr, len, owner, release }
+  }
+
+  /// Views the data.
+  #[inline(always)]
+  pub fn as_shared(&self) -> SharedSlice<'_, u8> {
+    SharedSlice::from(self.deref())
+  }
+}
+
+impl Drop for StableBytes {
+  #[inline]
+  fn drop(&mut self) {
+    // Safety: We own the reference, and give it up here.
+    unsafe { (self.release)(self.owner) }
+  }
+}
+
+impl Deref for StableBytes {
+  type Target = [u8];
+  #[inline(always)]
+  fn deref(&self) -> &[u8] {
+    // Safety: See note at the top of the module.
+    unsafe { slice::from_raw_parts(self.ptr, self.len) }
+  }
+}
+
+impl AsRef<[u8]> for StableBytes {
+  #[inline(always)]
+  fn as_ref(&self) -> &[u8] {
+    self
+  }
+}
+
+impl Debug for StableBytes {
+  /// Debug prints as a slice would.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    Debug::fmt(self.deref(), f)
+  }
+}
+
+impl From<Bytes> for StableBytes {
+  /// Boxes the `Bytes` handle, without copying the data.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let payload = bytes::Bytes::from_static(b"hello");
+  /// let buf = StableBytes::from(payload.clone());
+  /// assert_eq!(buf.as_ptr(), payload.as_ptr());
+  /// let back = bytes::Bytes::from(buf);
+  /// assert_eq!(back.as_ptr(), payload.as_ptr());
+  /// ```
+  #[inline]
+  fn from(b: Bytes) -> Self {
+    unsafe extern "C" fn release_bytes(owner: *mut c_void) {
+      drop(Box::from_raw(owner as *mut Bytes))
+    }
+    trace_conversion!(Bytes => Self, b.as_ptr(), b.len());
+    let ptr = b.as_ptr();
+    let len = b.len();
+    let owner = Box::into_raw(Box::new(b)) as *mut c_void;
+    Self { ptr, len, owner, release: release_bytes }
+  }
+}
+
+impl From<StableBytes> for Bytes {
+  /// Wraps the buffer without copying the data. The buffer is released when
+  /// the last clone of the new `Bytes` is dropped.
+  #[inline]
+  fn from(buf: StableBytes) -> Self {
+    trace_conversion!(StableBytes => Self, buf.ptr, buf.len);
+    Bytes::from_owner(buf)
+  }
+}
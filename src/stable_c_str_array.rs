@@ -0,0 +1,122 @@
+#![cfg(feature = "unsafe_alloc")]
+
+use super::{SharedCStrArray, StableLayout, StableVec};
+use alloc::{
+  ffi::{CString, NulError},
+  vec::Vec,
+};
+use core::{
+  ffi::{c_char, CStr},
+  fmt::Debug,
+};
+
+/// An owned, null terminated array of C strings with a stable layout.
+///
+/// This builds the `argv` / `envp` shape that [`SharedCStrArray`] views. The
+/// data layout is the same as a `StableVec<*mut c_char>`, where the last
+/// element is always a null pointer and every other element is a string
+/// allocated by `CString`.
+///
+/// Like the other owned stable types this leaks its memory if you drop it
+/// without turning it back into a Rust type, which here is a `Vec<CString>`.
+///
+/// ```rust
+/// # use chromium::*;
+/// let args = StableCStrArray::try_from_strs(&["prog", "--fast"]).unwrap();
+/// assert_eq!(args.len(), 2);
+/// let mut it = args.as_shared().iter();
+/// assert_eq!(it.next().unwrap().to_bytes(), b"prog");
+/// assert_eq!(it.next().unwrap().to_bytes(), b"--fast");
+/// assert!(it.next().is_none());
+/// let _strings = Vec::<std::ffi::CString>::from(args);
+/// ```
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StableCStrArray`
+/// typedef struct {
+///   char **ptr; // ptr[len] is always NULL
+///   uintptr_t len; // includes the NULL
+///   uintptr_t cap;
+/// } StableCStrArray;
+/// ```
+#[repr(transparent)]
+pub struct StableCStrArray {
+  ptrs: StableVec<*mut c_char>,
+}
+
+unsafe impl StableLayout for StableCStrArray {}
+
+impl StableCStrArray {
+  /// Copies some strings into a new array.
+  ///
+  /// Fails if any of the strings contains a null byte.
+  #[inline]
+  pub fn try_from_strs(strs: &[&str]) -> Result<Self, NulError> {
+    let strings: Result<Vec<CString>, NulError> =
+      strs.iter().map(|&s| CString::new(s)).collect();
+    strings.map(Self::from)
+  }
+
+  /// The number of strings, not counting the terminating null.
+  #[inline(always)]
+  pub fn len(&self) -> usize {
+    self.ptrs.len() - 1
+  }
+
+  /// If there are no strings.
+  #[inline(always)]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Borrows the array as a shared view.
+  #[inline(always)]
+  pub fn as_shared(&self) -> SharedCStrArray<'_> {
+    // Safety: The array always ends in a null pointer, and every other
+    // element is a valid string from `CString::into_raw`.
+    unsafe { SharedCStrArray::from_ptr(self.ptrs.as_ptr() as *const _) }
+  }
+}
+
+impl Debug for StableCStrArray {
+  /// Debug prints as a list of strings.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    Debug::fmt(&self.as_shared(), f)
+  }
+}
+
+impl Default for StableCStrArray {
+  /// Defaults to an empty array (a lone null pointer).
+  #[inline]
+  fn default() -> Self {
+    Self::from(Vec::new())
+  }
+}
+
+impl From<Vec<CString>> for StableCStrArray {
+  #[inline]
+  fn from(strings: Vec<CString>) -> Self {
+    let mut ptrs: Vec<*mut c_char> =
+      strings.into_iter().map(CString::into_raw).collect();
+    ptrs.push(core::ptr::null_mut());
+    Self { ptrs: StableVec::from(ptrs) }
+  }
+}
+
+impl From<&[&CStr]> for StableCStrArray {
+  #[inline]
+  fn from(strs: &[&CStr]) -> Self {
+    Self::from(strs.iter().map(|&s| CString::from(s)).collect::<Vec<_>>())
+  }
+}
+
+impl From<StableCStrArray> for Vec<CString> {
+  #[track_caller]
+  fn from(array: StableCStrArray) -> Self {
+    let mut ptrs = Vec::from(array.ptrs);
+    ptrs.pop();
+    // Safety: Every pointer other than the null came from `CString::into_raw`.
+    ptrs.into_iter().map(|p| unsafe { CString::from_raw(p) }).collect()
+  }
+}
@@ -0,0 +1,314 @@
+//! Comparison and hashing impls for the slice and string types.
+//!
+//! Everything here compares by content, the same as the Rust type that each
+//! stable type stands in for, so a `SharedSlice` equals a `UniqueSlice` with
+//! the same elements, orders the same as a `[T]` would (lexicographically),
+//! and hashes the same as a `[T]` would. They also compare equal (both ways
+//! around) to the matching native types, such as `[T]`, `&[T]`, `Vec<T>`,
+//! `str`, `&str`, and `String`.
+
+use core::{
+  cmp::Ordering,
+  hash::{Hash, Hasher},
+};
+
+use super::{SharedSlice, SharedStr, StableLayout, UniqueSlice, UniqueStr};
+
+#[cfg(feature = "unsafe_alloc")]
+use super::{StableString, StableVec};
+#[cfg(feature = "unsafe_alloc")]
+use alloc::{string::String, vec::Vec};
+
+/// Content equality (with any comparable elements) and ordering (with the
+/// same element type) between two slice types.
+macro_rules! impl_slice_cmp {
+  (
+    $(#[$attr:meta])*
+    $lhs:ident $(<$llife:lifetime>)?, $rhs:ident $(<$rlife:lifetime>)?
+  ) => {
+    $(#[$attr])*
+    impl<$($llife,)? $($rlife,)? T, U> PartialEq<$rhs<$($rlife,)? U>>
+      for $lhs<$($llife,)? T>
+    where
+      T: StableLayout + PartialEq<U>,
+      U: StableLayout,
+    {
+      #[inline]
+      fn eq(&self, other: &$rhs<$($rlife,)? U>) -> bool {
+        self[..] == other[..]
+      }
+    }
+
+    $(#[$attr])*
+    impl<$($llife,)? $($rlife,)? T> PartialOrd<$rhs<$($rlife,)? T>>
+      for $lhs<$($llife,)? T>
+    where
+      T: StableLayout + PartialOrd,
+    {
+      #[inline]
+      fn partial_cmp(
+        &self, other: &$rhs<$($rlife,)? T>,
+      ) -> Option<Ordering> {
+        self[..].partial_cmp(&other[..])
+      }
+    }
+  };
+}
+
+/// Content equality and ordering between two string types.
+macro_rules! impl_str_cmp {
+  ($(#[$attr:meta])* [$($life:lifetime),*] $lhs:ty, $rhs:ty) => {
+    $(#[$attr])*
+    impl<$($life),*> PartialEq<$rhs> for $lhs {
+      #[inline]
+      fn eq(&self, other: &$rhs) -> bool {
+        self[..] == other[..]
+      }
+    }
+
+    $(#[$attr])*
+    impl<$($life),*> PartialOrd<$rhs> for $lhs {
+      #[inline]
+      fn partial_cmp(&self, other: &$rhs) -> Option<Ordering> {
+        self[..].partial_cmp(&other[..])
+      }
+    }
+  };
+}
+
+/// Content equality, both ways around, between a slice type (with elements
+/// `T`) and a native type (with elements `U`).
+macro_rules! impl_slice_eq_native {
+  (
+    $(#[$attr:meta])*
+    [$($life:lifetime),*] $(const $n:ident,)? $wrapper:ty, $native:ty
+  ) => {
+    $(#[$attr])*
+    impl<$($life,)* T, U $(, const $n: usize)?> PartialEq<$native> for $wrapper
+    where
+      T: StableLayout + PartialEq<U>,
+    {
+      #[inline]
+      fn eq(&self, other: &$native) -> bool {
+        self[..] == other[..]
+      }
+    }
+
+    $(#[$attr])*
+    impl<$($life,)* T, U $(, const $n: usize)?> PartialEq<$wrapper> for $native
+    where
+      T: StableLayout,
+      U: PartialEq<T>,
+    {
+      #[inline]
+      fn eq(&self, other: &$wrapper) -> bool {
+        self[..] == other[..]
+      }
+    }
+  };
+}
+
+/// Content equality, both ways around, between a string type and a native
+/// type.
+macro_rules! impl_str_eq_native {
+  ($(#[$attr:meta])* [$($life:lifetime),*] $wrapper:ty, $native:ty) => {
+    $(#[$attr])*
+    impl<$($life),*> PartialEq<$native> for $wrapper {
+      #[inline]
+      fn eq(&self, other: &$native) -> bool {
+        self[..] == other[..]
+      }
+    }
+
+    $(#[$attr])*
+    impl<$($life),*> PartialEq<$wrapper> for $native {
+      #[inline]
+      fn eq(&self, other: &$wrapper) -> bool {
+        self[..] == other[..]
+      }
+    }
+  };
+}
+
+/// `Eq`, `Ord`, and `Hash` for a slice type, by content.
+macro_rules! impl_slice_eq_ord_hash {
+  ($(#[$attr:meta])* [$($life:lifetime),*] $t:ty) => {
+    $(#[$attr])*
+    impl<$($life,)* T> Eq for $t where T: StableLayout + Eq {}
+
+    $(#[$attr])*
+    impl<$($life,)* T> Ord for $t
+    where
+      T: StableLayout + Ord,
+    {
+      #[inline]
+      fn cmp(&self, other: &Self) -> Ordering {
+        self[..].cmp(&other[..])
+      }
+    }
+
+    $(#[$attr])*
+    impl<$($life,)* T> Hash for $t
+    where
+      T: StableLayout + Hash,
+    {
+      #[inline]
+      fn hash<H: Hasher>(&self, state: &mut H) {
+        self[..].hash(state)
+      }
+    }
+  };
+}
+
+/// `Eq`, `Ord`, and `Hash` for a string type, by content.
+macro_rules! impl_str_eq_ord_hash {
+  ($(#[$attr:meta])* [$($life:lifetime),*] $t:ty) => {
+    $(#[$attr])*
+    impl<$($life),*> Eq for $t {}
+
+    $(#[$attr])*
+    impl<$($life),*> Ord for $t {
+      #[inline]
+      fn cmp(&self, other: &Self) -> Ordering {
+        self[..].cmp(&other[..])
+      }
+    }
+
+    $(#[$attr])*
+    impl<$($life),*> Hash for $t {
+      #[inline]
+      fn hash<H: Hasher>(&self, state: &mut H) {
+        self[..].hash(state)
+      }
+    }
+  };
+}
+
+impl_slice_cmp!(SharedSlice<'a>, SharedSlice<'b>);
+impl_slice_cmp!(SharedSlice<'a>, UniqueSlice<'b>);
+impl_slice_cmp!(UniqueSlice<'a>, SharedSlice<'b>);
+impl_slice_cmp!(UniqueSlice<'a>, UniqueSlice<'b>);
+impl_slice_cmp!(
+  #[cfg(feature = "unsafe_alloc")]
+  SharedSlice<'a>, StableVec
+);
+impl_slice_cmp!(
+  #[cfg(feature = "unsafe_alloc")]
+  UniqueSlice<'a>, StableVec
+);
+impl_slice_cmp!(
+  #[cfg(feature = "unsafe_alloc")]
+  StableVec,
+  SharedSlice<'b>
+);
+impl_slice_cmp!(
+  #[cfg(feature = "unsafe_alloc")]
+  StableVec,
+  UniqueSlice<'b>
+);
+impl_slice_cmp!(
+  #[cfg(feature = "unsafe_alloc")]
+  StableVec,
+  StableVec
+);
+
+impl_str_cmp!(['a, 'b] SharedStr<'a>, SharedStr<'b>);
+impl_str_cmp!(['a, 'b] SharedStr<'a>, UniqueStr<'b>);
+impl_str_cmp!(['a, 'b] UniqueStr<'a>, SharedStr<'b>);
+impl_str_cmp!(['a, 'b] UniqueStr<'a>, UniqueStr<'b>);
+impl_str_cmp!(
+  #[cfg(feature = "unsafe_alloc")]
+  ['a] SharedStr<'a>, StableString
+);
+impl_str_cmp!(
+  #[cfg(feature = "unsafe_alloc")]
+  ['a] UniqueStr<'a>, StableString
+);
+impl_str_cmp!(
+  #[cfg(feature = "unsafe_alloc")]
+  ['b] StableString, SharedStr<'b>
+);
+impl_str_cmp!(
+  #[cfg(feature = "unsafe_alloc")]
+  ['b] StableString, UniqueStr<'b>
+);
+impl_str_cmp!(
+  #[cfg(feature = "unsafe_alloc")]
+  // It's the same as the `Ord` impl, just not spelled that way.
+  #[allow(clippy::non_canonical_partial_ord_impl)]
+  [] StableString, StableString
+);
+
+impl_slice_eq_native!(['a] SharedSlice<'a, T>, [U]);
+impl_slice_eq_native!(['a, 'b] SharedSlice<'a, T>, &'b [U]);
+impl_slice_eq_native!(['a] const N, SharedSlice<'a, T>, [U; N]);
+impl_slice_eq_native!(['a, 'b] const N, SharedSlice<'a, T>, &'b [U; N]);
+impl_slice_eq_native!(
+  #[cfg(feature = "unsafe_alloc")]
+  ['a] SharedSlice<'a, T>, Vec<U>
+);
+impl_slice_eq_native!(['a] UniqueSlice<'a, T>, [U]);
+impl_slice_eq_native!(['a, 'b] UniqueSlice<'a, T>, &'b [U]);
+impl_slice_eq_native!(['a] const N, UniqueSlice<'a, T>, [U; N]);
+impl_slice_eq_native!(['a, 'b] const N, UniqueSlice<'a, T>, &'b [U; N]);
+impl_slice_eq_native!(
+  #[cfg(feature = "unsafe_alloc")]
+  ['a] UniqueSlice<'a, T>, Vec<U>
+);
+impl_slice_eq_native!(
+  #[cfg(feature = "unsafe_alloc")]
+  [] StableVec<T>, [U]
+);
+impl_slice_eq_native!(
+  #[cfg(feature = "unsafe_alloc")]
+  ['b] StableVec<T>, &'b [U]
+);
+impl_slice_eq_native!(
+  #[cfg(feature = "unsafe_alloc")]
+  [] const N, StableVec<T>, [U; N]
+);
+impl_slice_eq_native!(
+  #[cfg(feature = "unsafe_alloc")]
+  ['b] const N, StableVec<T>, &'b [U; N]
+);
+impl_slice_eq_native!(
+  #[cfg(feature = "unsafe_alloc")]
+  [] StableVec<T>, Vec<U>
+);
+impl_str_eq_native!(['a] SharedStr<'a>, str);
+impl_str_eq_native!(['a, 'b] SharedStr<'a>, &'b str);
+impl_str_eq_native!(
+  #[cfg(feature = "unsafe_alloc")]
+  ['a] SharedStr<'a>, String
+);
+impl_str_eq_native!(['a] UniqueStr<'a>, str);
+impl_str_eq_native!(['a, 'b] UniqueStr<'a>, &'b str);
+impl_str_eq_native!(
+  #[cfg(feature = "unsafe_alloc")]
+  ['a] UniqueStr<'a>, String
+);
+impl_str_eq_native!(
+  #[cfg(feature = "unsafe_alloc")]
+  [] StableString, str
+);
+impl_str_eq_native!(
+  #[cfg(feature = "unsafe_alloc")]
+  ['b] StableString, &'b str
+);
+impl_str_eq_native!(
+  #[cfg(feature = "unsafe_alloc")]
+  [] StableString, String
+);
+
+impl_slice_eq_ord_hash!(['a] SharedSlice<'a, T>);
+impl_slice_eq_ord_hash!(['a] UniqueSlice<'a, T>);
+impl_slice_eq_ord_hash!(
+  #[cfg(feature = "unsafe_alloc")]
+  [] StableVec<T>
+);
+impl_str_eq_ord_hash!(['a] SharedStr<'a>);
+impl_str_eq_ord_hash!(['a] UniqueStr<'a>);
+impl_str_eq_ord_hash!(
+  #[cfg(feature = "unsafe_alloc")]
+  [] StableString
+);
@@ -0,0 +1,171 @@
+#![cfg(feature = "unsafe_alloc")]
+
+use super::{StableLayout, StableStatus, StableString, StableVec};
+use alloc::{string::String, vec::Vec};
+use core::fmt::{Debug, Display};
+
+/// An error report with a stable layout.
+///
+/// This is a [`StableStatus`] code along with a chain of messages, outermost
+/// first: the first message says what went wrong, and each message after it
+/// is the cause of the one before. It's for reporting errors across a C ABI
+/// without losing the context that Rust code usually attaches to them.
+///
+/// `Display` prints the first message, and the alternate form (`{:#}`) prints
+/// the whole chain separated by `": "`, the same as `anyhow` does.
+///
+/// Like a `StableVec`, the messages leak if you drop the error without
+/// turning it back into a Rust type, such as with
+/// [`into_messages`](Self::into_messages).
+///
+/// ## Unsafety
+///
+/// This has all the same invariants as `StableVec<StableString>`.
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StableError`
+/// typedef struct {
+///   int32_t status;
+///   StableVec_StableString messages; // { ptr, len, cap }
+/// } StableError;
+/// ```
+#[repr(C)]
+pub struct StableError {
+  /// The status code for the error as a whole.
+  pub status: StableStatus,
+  messages: StableVec<StableString>,
+}
+
+unsafe impl StableLayout for StableError {}
+
+impl StableError {
+  /// Makes an error with a single message.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let err = StableError::new(StableStatus::NOT_FOUND, "no such file")
+  ///   .with_context("couldn't load the config");
+  /// assert_eq!(format!("{}", err), "couldn't load the config");
+  /// assert_eq!(
+  ///   format!("{:#}", err),
+  ///   "couldn't load the config: no such file"
+  /// );
+  /// assert_eq!(err.status, StableStatus::NOT_FOUND);
+  /// assert_eq!(err.into_messages().len(), 2);
+  /// ```
+  #[inline]
+  pub fn new(status: StableStatus, message: &str) -> Self {
+    Self::from_messages(status, core::iter::once(String::from(message)))
+  }
+
+  /// Makes an error from a chain of messages, outermost first.
+  #[inline]
+  pub fn from_messages<I>(status: StableStatus, messages: I) -> Self
+  where
+    I: IntoIterator<Item = String>,
+  {
+    let messages: Vec<StableString> =
+      messages.into_iter().map(StableString::from).collect();
+    Self { status, messages: StableVec::from(messages) }
+  }
+
+  /// Adds a new outermost message, with the old messages as its cause.
+  #[inline]
+  #[track_caller]
+  pub fn with_context(mut self, context: &str) -> Self {
+    let mut messages = Vec::from(self.messages);
+    messages.insert(0, StableString::from(String::from(context)));
+    self.messages = StableVec::from(messages);
+    self
+  }
+
+  /// The outermost message, or an empty string if there are no messages.
+  #[inline]
+  pub fn message(&self) -> &str {
+    self.messages.first().map(|s| &**s).unwrap_or("")
+  }
+
+  /// Iterates over the messages, outermost first.
+  #[inline]
+  pub fn messages(&self) -> impl Iterator<Item = &str> + '_ {
+    self.messages.iter().map(|s| &**s)
+  }
+
+  /// Takes back the messages, outermost first.
+  #[inline]
+  #[track_caller]
+  pub fn into_messages(self) -> Vec<String> {
+    Vec::from(self.messages).into_iter().map(String::from).collect()
+  }
+}
+
+impl Debug for StableError {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_struct("StableError")
+      .field("status", &self.status)
+      .field("messages", &self.messages)
+      .finish()
+  }
+}
+
+impl Display for StableError {
+  /// Prints the outermost message, or the whole chain in the alternate form.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if f.alternate() {
+      for (i, message) in self.messages().enumerate() {
+        if i > 0 {
+          f.write_str(": ")?;
+        }
+        f.write_str(message)?;
+      }
+      Ok(())
+    } else {
+      f.write_str(self.message())
+    }
+  }
+}
+
+#[cfg(feature = "anyhow")]
+impl From<anyhow::Error> for StableError {
+  /// Keeps the message of every error in the chain.
+  ///
+  /// The status is taken from a [`StableStatus`] anywhere in the chain (such
+  /// as one given to `anyhow::Error::msg` or `context`), and is otherwise
+  /// [`StableStatus::OTHER`].
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// use anyhow::Context;
+  /// let result: anyhow::Result<()> = Err(anyhow::anyhow!("disk full"))
+  ///   .context("couldn't save")
+  ///   .context(StableStatus::WRITE_ZERO);
+  /// let err = StableError::from(result.unwrap_err());
+  /// assert_eq!(err.status, StableStatus::WRITE_ZERO);
+  /// let back = anyhow::Error::from(err);
+  /// assert_eq!(format!("{:#}", back), "WRITE_ZERO: couldn't save: disk full");
+  /// ```
+  #[inline]
+  fn from(error: anyhow::Error) -> Self {
+    let status = error
+      .downcast_ref::<StableStatus>()
+      .copied()
+      .unwrap_or(StableStatus::OTHER);
+    let messages = error.chain().map(alloc::string::ToString::to_string);
+    Self::from_messages(status, messages)
+  }
+}
+
+#[cfg(feature = "anyhow")]
+impl From<StableError> for anyhow::Error {
+  /// Rebuilds the chain of messages, with each message as the context of the
+  /// next. The original error types are gone, so the messages are all that
+  /// remains of them, and the status isn't kept.
+  #[inline]
+  #[track_caller]
+  fn from(error: StableError) -> Self {
+    let mut messages = error.into_messages().into_iter().rev();
+    let root = messages.next().unwrap_or_default();
+    messages.fold(anyhow::Error::msg(root), |inner, outer| inner.context(outer))
+  }
+}
@@ -0,0 +1,185 @@
+use core::fmt::Debug;
+
+use super::StableLayout;
+
+/// A fixed size set of feature bits with a stable layout.
+///
+/// Each side of an FFI boundary describes the optional capabilities it
+/// supports as bit numbers in one of these. Since the set is always 256 bits,
+/// adding a new capability never changes any struct layouts, it just gets the
+/// next bit number. See [`negotiate`](Self::negotiate) for agreeing on a set.
+///
+/// Bit numbers of 256 or more are outside of the set: they're never
+/// contained, and setting them does nothing.
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StableFeatureFlags`
+/// typedef struct {
+///   uint64_t words[4]; // bit `n` is `words[n / 64] & (1 << (n % 64))`
+/// } StableFeatureFlags;
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct StableFeatureFlags {
+  words: [u64; 4],
+}
+
+unsafe impl StableLayout for StableFeatureFlags {}
+
+impl StableFeatureFlags {
+  /// The number of bits in the set.
+  pub const BITS: usize = 256;
+
+  /// The empty set.
+  #[inline(always)]
+  pub const fn empty() -> Self {
+    Self { words: [0; 4] }
+  }
+
+  /// Makes a set from its raw words.
+  #[inline(always)]
+  pub const fn from_words(words: [u64; 4]) -> Self {
+    Self { words }
+  }
+
+  /// The raw words of the set.
+  #[inline(always)]
+  pub const fn to_words(self) -> [u64; 4] {
+    self.words
+  }
+
+  /// Makes a set of the given bits.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let flags = StableFeatureFlags::from_bits(&[0, 3, 200]);
+  /// assert!(flags.contains(3));
+  /// assert!(!flags.contains(4));
+  /// assert_eq!(flags.iter().collect::<Vec<_>>(), vec![0, 3, 200]);
+  /// ```
+  #[inline]
+  pub const fn from_bits(bits: &[usize]) -> Self {
+    let mut out = Self::empty();
+    let mut i = 0;
+    while i < bits.len() {
+      out = out.with(bits[i]);
+      i += 1;
+    }
+    out
+  }
+
+  /// This set with a bit added.
+  #[inline(always)]
+  pub const fn with(mut self, bit: usize) -> Self {
+    if bit < Self::BITS {
+      self.words[bit / 64] |= 1 << (bit % 64);
+    }
+    self
+  }
+
+  /// This set with a bit removed.
+  #[inline(always)]
+  pub const fn without(mut self, bit: usize) -> Self {
+    if bit < Self::BITS {
+      self.words[bit / 64] &= !(1 << (bit % 64));
+    }
+    self
+  }
+
+  /// If the bit is in the set.
+  #[inline(always)]
+  pub const fn contains(&self, bit: usize) -> bool {
+    bit < Self::BITS && (self.words[bit / 64] & (1 << (bit % 64))) != 0
+  }
+
+  /// If no bits are in the set.
+  #[inline(always)]
+  pub const fn is_empty(&self) -> bool {
+    let [a, b, c, d] = self.words;
+    (a | b | c | d) == 0
+  }
+
+  /// The number of bits in the set.
+  #[inline]
+  pub const fn count(&self) -> u32 {
+    let [a, b, c, d] = self.words;
+    a.count_ones() + b.count_ones() + c.count_ones() + d.count_ones()
+  }
+
+  /// The bits in both sets.
+  #[inline(always)]
+  pub const fn intersection(self, other: Self) -> Self {
+    let [a, b, c, d] = self.words;
+    let [e, f, g, h] = other.words;
+    Self { words: [a & e, b & f, c & g, d & h] }
+  }
+
+  /// The bits in either set.
+  #[inline(always)]
+  pub const fn union(self, other: Self) -> Self {
+    let [a, b, c, d] = self.words;
+    let [e, f, g, h] = other.words;
+    Self { words: [a | e, b | f, c | g, d | h] }
+  }
+
+  /// The bits in `self` but not in `other`.
+  #[inline(always)]
+  pub const fn difference(self, other: Self) -> Self {
+    let [a, b, c, d] = self.words;
+    let [e, f, g, h] = other.words;
+    Self { words: [a & !e, b & !f, c & !g, d & !h] }
+  }
+
+  /// Iterates over the bits in the set, from lowest to highest.
+  #[inline]
+  pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+    (0..Self::BITS).filter(move |&bit| self.contains(bit))
+  }
+
+  /// Agrees on a set of features between two sides.
+  ///
+  /// * `offered` is everything that one side (usually the host) supports.
+  /// * `supported` is everything that the other side (usually the plugin)
+  ///   supports.
+  /// * `required` is the subset of `supported` that the other side can't work
+  ///   without.
+  ///
+  /// If all of `required` is offered, this gives the features that both sides
+  /// support. Otherwise it gives the required features that weren't offered.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// const HDR: usize = 0;
+  /// const VSYNC: usize = 1;
+  /// const COMPUTE: usize = 2;
+  /// let host = StableFeatureFlags::from_bits(&[HDR, VSYNC]);
+  /// let plugin = StableFeatureFlags::from_bits(&[VSYNC, COMPUTE]);
+  ///
+  /// let optional = StableFeatureFlags::empty();
+  /// let agreed = StableFeatureFlags::negotiate(host, plugin, optional);
+  /// assert_eq!(agreed, Ok(StableFeatureFlags::from_bits(&[VSYNC])));
+  ///
+  /// let needs_compute = StableFeatureFlags::from_bits(&[COMPUTE]);
+  /// let missing = StableFeatureFlags::negotiate(host, plugin, needs_compute);
+  /// assert_eq!(missing, Err(needs_compute));
+  /// ```
+  #[inline]
+  pub const fn negotiate(
+    offered: Self, supported: Self, required: Self,
+  ) -> Result<Self, Self> {
+    let missing = required.difference(offered);
+    if missing.is_empty() {
+      Ok(offered.intersection(supported))
+    } else {
+      Err(missing)
+    }
+  }
+}
+
+impl Debug for StableFeatureFlags {
+  /// Debug prints as a set of bit numbers.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_set().entries(self.iter()).finish()
+  }
+}
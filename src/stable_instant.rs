@@ -0,0 +1,125 @@
+use core::{convert::TryFrom, time::Duration};
+
+use super::StableLayout;
+
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// A monotonic timestamp with a stable layout.
+///
+/// An `Instant` is opaque, so it can't cross an FFI boundary directly. Instead
+/// both sides agree on some epoch (usually an `Instant` taken by the host when
+/// it starts up) and then a timestamp is the number of nanoseconds since that
+/// epoch.
+///
+/// The `clock` field is a tag for which epoch a timestamp is measured from.
+/// Timestamps from different clocks can't be compared, and the methods here
+/// will return `None` if you try. The meaning of each clock value is up to the
+/// program, though 0 is conventionally the host's main clock.
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StableInstant`
+/// typedef struct {
+///   uint64_t nanos;
+///   uint32_t clock;
+/// } StableInstant;
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct StableInstant {
+  /// Nanoseconds since the clock's epoch.
+  pub nanos: u64,
+  /// Which clock (and so which epoch) this timestamp is from.
+  pub clock: u32,
+}
+
+unsafe impl StableLayout for StableInstant {}
+
+impl StableInstant {
+  /// Makes a timestamp `nanos` after the epoch of `clock`.
+  #[inline(always)]
+  pub const fn new(nanos: u64, clock: u32) -> Self {
+    Self { nanos, clock }
+  }
+
+  /// The time that passed from `earlier` to `self`.
+  ///
+  /// Gives `None` if the timestamps are from different clocks, or if `earlier`
+  /// is actually later than `self`.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// # use core::time::Duration;
+  /// let a = StableInstant::new(1_000, 0);
+  /// let b = StableInstant::new(3_500, 0);
+  /// assert_eq!(b.duration_since(a), Some(Duration::from_nanos(2_500)));
+  /// assert_eq!(a.duration_since(b), None);
+  /// assert_eq!(b.duration_since(StableInstant::new(0, 1)), None);
+  /// ```
+  #[inline]
+  pub fn duration_since(&self, earlier: Self) -> Option<Duration> {
+    if self.clock != earlier.clock {
+      return None;
+    }
+    self.nanos.checked_sub(earlier.nanos).map(Duration::from_nanos)
+  }
+
+  /// Moves the timestamp forward, or gives `None` on overflow.
+  #[inline]
+  pub fn checked_add(&self, duration: Duration) -> Option<Self> {
+    let delta = u64::try_from(duration.as_nanos()).ok()?;
+    let nanos = self.nanos.checked_add(delta)?;
+    Some(Self { nanos, clock: self.clock })
+  }
+
+  /// Moves the timestamp backward, or gives `None` if that would go past the
+  /// epoch.
+  #[inline]
+  pub fn checked_sub(&self, duration: Duration) -> Option<Self> {
+    let delta = u64::try_from(duration.as_nanos()).ok()?;
+    let nanos = self.nanos.checked_sub(delta)?;
+    Some(Self { nanos, clock: self.clock })
+  }
+
+  /// Measures `instant` relative to `epoch`.
+  ///
+  /// If `instant` is before `epoch` this saturates to 0 nanoseconds, and if
+  /// it's more than about 584 years after the `epoch` it saturates to
+  /// `u64::MAX` nanoseconds.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// # use std::time::{Duration, Instant};
+  /// let epoch = Instant::now();
+  /// let later = epoch + Duration::from_millis(5);
+  /// let stable = StableInstant::from_instant(later, epoch, 0);
+  /// assert_eq!(stable.nanos, 5_000_000);
+  /// assert_eq!(stable.checked_to_instant(epoch), Some(later));
+  /// ```
+  #[cfg(feature = "std")]
+  #[inline]
+  pub fn from_instant(instant: Instant, epoch: Instant, clock: u32) -> Self {
+    let since = instant.saturating_duration_since(epoch).as_nanos();
+    let nanos = u64::try_from(since).unwrap_or(u64::MAX);
+    Self { nanos, clock }
+  }
+
+  /// Turns the timestamp back into an `Instant`, given the clock's epoch.
+  ///
+  /// ## Panics
+  /// If the result can't be represented by an `Instant`.
+  #[cfg(feature = "std")]
+  #[inline]
+  pub fn to_instant(&self, epoch: Instant) -> Instant {
+    epoch + Duration::from_nanos(self.nanos)
+  }
+
+  /// Turns the timestamp back into an `Instant`, given the clock's epoch, or
+  /// gives `None` if the result can't be represented by an `Instant`.
+  #[cfg(feature = "std")]
+  #[inline]
+  pub fn checked_to_instant(&self, epoch: Instant) -> Option<Instant> {
+    epoch.checked_add(Duration::from_nanos(self.nanos))
+  }
+}
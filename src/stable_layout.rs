@@ -5,8 +5,16 @@
 /// The type's [Type Layout][type-layout] must fit one of the following:
 /// * [Primitive][prim] layout types of 64-bits or less.
 ///   * **Examples:** `i8`, `u32`
+/// * 128-bit primitive types, but only where their layout matches the C ABI's
+///   128-bit type. That isn't true on every target and compiler version, so
+///   these impls are opt-in behind a feature.
+///   * **Examples:** `u128` and `i128` (and their `NonZero` types) with the
+///     `int128` feature, `f128` with the `nightly_f16_f128` feature.
 /// * Any zero-sized type (ZST).
 ///   * **Examples:** `()`
+/// * Any uninhabited type with no fields, which is a ZST that can't ever
+///   actually exist.
+///   * **Examples:** [`Infallible`](core::convert::Infallible)
 /// * [`repr(C)`][repr-c] `struct` or `union` types when all fields are also
 ///   `StableLayout`.
 ///   * **Examples:** Most `libc` and `winapi` types.
@@ -22,6 +30,12 @@
 /// * Uninitialized bytes, such as padding bytes.
 /// * Invalid bit patterns, such as `bool` and `char`.
 ///
+/// An uninhabited type is the extreme case of invalid bit patterns: _every_
+/// bit pattern is invalid. A `repr(C)` type with an uninhabited field can't
+/// exist either, but a tagged type such as `StableResult<T, Infallible>` is
+/// fine, it just can never hold an error. Foreign code must never write a tag
+/// that claims to hold the uninhabited value.
+///
 /// [type-layout]: https://doc.rust-lang.org/stable/reference/type-layout.html
 /// [prim]:
 /// https://doc.rust-lang.org/stable/reference/type-layout.html#primitive-representations
@@ -43,13 +57,33 @@ unsafe impl StableLayout for i32 {}
 unsafe impl StableLayout for i64 {}
 unsafe impl StableLayout for isize {}
 
+// Before Rust 1.77 the 128-bit integers only had 8 byte alignment on x86 and
+// x86_64, while C's `__int128` has 16 byte alignment. Some C compilers also
+// don't have a 128-bit integer at all on some targets. So these are opt-in.
+#[cfg(feature = "int128")]
+unsafe impl StableLayout for u128 {}
+#[cfg(feature = "int128")]
+unsafe impl StableLayout for i128 {}
+
 unsafe impl StableLayout for f32 {}
 unsafe impl StableLayout for f64 {}
+#[cfg(feature = "nightly_f16_f128")]
+unsafe impl StableLayout for f16 {}
+#[cfg(feature = "nightly_f16_f128")]
+unsafe impl StableLayout for f128 {}
 
 unsafe impl StableLayout for bool {}
 unsafe impl StableLayout for char {}
 unsafe impl StableLayout for () {}
 
+// `c_void` is never actually created, it's only ever used behind a pointer as
+// C's `void *`. We mark it so that `*mut c_void` counts.
+use core::ffi::c_void;
+unsafe impl StableLayout for c_void {}
+
+use core::convert::Infallible;
+unsafe impl StableLayout for Infallible {}
+
 use core::marker::PhantomData;
 /// `PhantomData` is a zero-sized type and so technically it could be defined as
 /// always being `StableLayout`. However, since `PhantomData` is semantically
@@ -58,9 +92,23 @@ use core::marker::PhantomData;
 /// `StableLayout` type.
 unsafe impl<T> StableLayout for PhantomData<T> where T: StableLayout {}
 
+use core::marker::PhantomPinned;
+unsafe impl StableLayout for PhantomPinned {}
+
 use core::num::Wrapping;
 unsafe impl<T> StableLayout for Wrapping<T> where T: StableLayout {}
 
+use core::num::Saturating;
+unsafe impl<T> StableLayout for Saturating<T> where T: StableLayout {}
+
+use core::cmp::Reverse;
+unsafe impl<T> StableLayout for Reverse<T> where T: StableLayout {}
+
+// `Ordering` is a `repr(i8)` enum that's documented to be -1, 0, or 1. Like
+// with `bool`, foreign code must never send any other value.
+use core::cmp::Ordering;
+unsafe impl StableLayout for Ordering {}
+
 use core::mem::ManuallyDrop;
 unsafe impl<T> StableLayout for ManuallyDrop<T> where T: StableLayout {}
 
@@ -88,6 +136,17 @@ unsafe impl StableLayout for Option<NonZeroI32> {}
 unsafe impl StableLayout for Option<NonZeroI64> {}
 unsafe impl StableLayout for Option<NonZeroIsize> {}
 
+#[cfg(feature = "int128")]
+use core::num::{NonZeroI128, NonZeroU128};
+#[cfg(feature = "int128")]
+unsafe impl StableLayout for NonZeroU128 {}
+#[cfg(feature = "int128")]
+unsafe impl StableLayout for NonZeroI128 {}
+#[cfg(feature = "int128")]
+unsafe impl StableLayout for Option<NonZeroU128> {}
+#[cfg(feature = "int128")]
+unsafe impl StableLayout for Option<NonZeroI128> {}
+
 // Note(Lokathor): Technically the pointer itself is stable with just `Sized`,
 // even with if the pointed to data isn't stable. However, it's essentially
 // impossible to utilize the power of StableLayout if the pointed to data isn't
@@ -115,6 +174,51 @@ use core::cell::{Cell, UnsafeCell};
 unsafe impl<T> StableLayout for UnsafeCell<T> where T: StableLayout {}
 unsafe impl<T> StableLayout for Cell<T> where T: StableLayout {}
 
+// Each atomic type has the same size and bit validity as the primitive it
+// wraps, but its alignment is always equal to its size, which on some targets
+// (such as `AtomicU64` on 32-bit x86) is more than the primitive's alignment.
+// In C the matching type is `_Atomic` of the primitive, which has that same
+// alignment rule.
+#[cfg(target_has_atomic = "8")]
+use core::sync::atomic::{AtomicBool, AtomicI8, AtomicU8};
+#[cfg(target_has_atomic = "8")]
+unsafe impl StableLayout for AtomicBool {}
+#[cfg(target_has_atomic = "8")]
+unsafe impl StableLayout for AtomicU8 {}
+#[cfg(target_has_atomic = "8")]
+unsafe impl StableLayout for AtomicI8 {}
+#[cfg(target_has_atomic = "16")]
+use core::sync::atomic::{AtomicI16, AtomicU16};
+#[cfg(target_has_atomic = "16")]
+unsafe impl StableLayout for AtomicU16 {}
+#[cfg(target_has_atomic = "16")]
+unsafe impl StableLayout for AtomicI16 {}
+#[cfg(target_has_atomic = "32")]
+use core::sync::atomic::{AtomicI32, AtomicU32};
+#[cfg(target_has_atomic = "32")]
+unsafe impl StableLayout for AtomicU32 {}
+#[cfg(target_has_atomic = "32")]
+unsafe impl StableLayout for AtomicI32 {}
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::{AtomicI64, AtomicU64};
+#[cfg(target_has_atomic = "64")]
+unsafe impl StableLayout for AtomicU64 {}
+#[cfg(target_has_atomic = "64")]
+unsafe impl StableLayout for AtomicI64 {}
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::{AtomicIsize, AtomicUsize};
+#[cfg(target_has_atomic = "ptr")]
+unsafe impl StableLayout for AtomicUsize {}
+#[cfg(target_has_atomic = "ptr")]
+unsafe impl StableLayout for AtomicIsize {}
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::AtomicPtr;
+#[cfg(target_has_atomic = "ptr")]
+unsafe impl<T> StableLayout for AtomicPtr<T> where T: Sized + StableLayout {}
+
+use core::mem::MaybeUninit;
+unsafe impl<T> StableLayout for MaybeUninit<T> where T: StableLayout {}
+
 #[cfg(feature = "unsafe_alloc")]
 use alloc::boxed::Box;
 #[cfg(feature = "unsafe_alloc")]
@@ -122,17 +226,69 @@ unsafe impl<T> StableLayout for Box<T> where T: Sized + StableLayout {}
 #[cfg(feature = "unsafe_alloc")]
 unsafe impl<T> StableLayout for Option<Box<T>> where T: Sized + StableLayout {}
 
-macro_rules! impl_unsafe_marker_for_array {
-  ( $marker:ident , $( $n:expr ),* ) => {
-    $(unsafe impl<T> $marker for [T; $n] where T: $marker {})*
-  }
+// `Pin` is `repr(transparent)` over its pointer, and `Option` of a
+// `repr(transparent)` wrapper of a reference or `Box` still gets the null
+// pointer optimization.
+use core::pin::Pin;
+unsafe impl<T> StableLayout for Pin<&T> where T: Sized + StableLayout {}
+unsafe impl<T> StableLayout for Option<Pin<&T>> where T: Sized + StableLayout {}
+unsafe impl<T> StableLayout for Pin<&mut T> where T: Sized + StableLayout {}
+unsafe impl<T> StableLayout for Option<Pin<&mut T>> where T: Sized + StableLayout
+{}
+#[cfg(feature = "unsafe_alloc")]
+unsafe impl<T> StableLayout for Pin<Box<T>> where T: Sized + StableLayout {}
+#[cfg(feature = "unsafe_alloc")]
+unsafe impl<T> StableLayout for Option<Pin<Box<T>>> where T: Sized + StableLayout
+{}
+
+unsafe impl<T, const N: usize> StableLayout for [T; N] where T: StableLayout {}
+
+// A function pointer is always a single non-null pointer, no matter the ABI,
+// and `Option` of one is guaranteed to use the null niche. The argument and
+// return types must be `StableLayout` too, so that both sides of a call through
+// the pointer agree on what's being passed. Pointers to functions with
+// arguments that involve lifetimes, such as `fn(&u8)`, are higher-ranked types
+// and so aren't covered.
+macro_rules! impl_unsafe_marker_for_fn {
+  ( $marker:ident, [$($prefix:tt)*], ($($a:ident),*) ) => {
+    unsafe impl<R, $($a),*> $marker for $($prefix)* fn($($a),*) -> R
+    where
+      R: $marker,
+      $($a: $marker),*
+    {
+    }
+    unsafe impl<R, $($a),*> $marker for Option<$($prefix)* fn($($a),*) -> R>
+    where
+      R: $marker,
+      $($a: $marker),*
+    {
+    }
+  };
 }
 #[rustfmt::skip]
-impl_unsafe_marker_for_array!(
-  StableLayout, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
-  16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32,
-  48, 64, 96, 128, 256, 512, 1024, 2048, 4096
-);
+macro_rules! impl_unsafe_marker_for_fn_arities {
+  ( $marker:ident, $prefix:tt ) => {
+    impl_unsafe_marker_for_fn!($marker, $prefix, ());
+    impl_unsafe_marker_for_fn!($marker, $prefix, (A));
+    impl_unsafe_marker_for_fn!($marker, $prefix, (A, B));
+    impl_unsafe_marker_for_fn!($marker, $prefix, (A, B, C));
+    impl_unsafe_marker_for_fn!($marker, $prefix, (A, B, C, D));
+    impl_unsafe_marker_for_fn!($marker, $prefix, (A, B, C, D, E));
+    impl_unsafe_marker_for_fn!($marker, $prefix, (A, B, C, D, E, F));
+    impl_unsafe_marker_for_fn!($marker, $prefix, (A, B, C, D, E, F, G));
+    impl_unsafe_marker_for_fn!($marker, $prefix, (A, B, C, D, E, F, G, H));
+    impl_unsafe_marker_for_fn!($marker, $prefix, (A, B, C, D, E, F, G, H, I));
+    impl_unsafe_marker_for_fn!($marker, $prefix, (A, B, C, D, E, F, G, H, I, J));
+    impl_unsafe_marker_for_fn!($marker, $prefix, (A, B, C, D, E, F, G, H, I, J, K));
+    impl_unsafe_marker_for_fn!($marker, $prefix, (A, B, C, D, E, F, G, H, I, J, K, L));
+  };
+}
+impl_unsafe_marker_for_fn_arities!(StableLayout, []);
+impl_unsafe_marker_for_fn_arities!(StableLayout, [unsafe]);
+impl_unsafe_marker_for_fn_arities!(StableLayout, [extern "C"]);
+impl_unsafe_marker_for_fn_arities!(StableLayout, [unsafe extern "C"]);
+impl_unsafe_marker_for_fn_arities!(StableLayout, [extern "C-unwind"]);
+impl_unsafe_marker_for_fn_arities!(StableLayout, [unsafe extern "C-unwind"]);
 
 #[cfg(target_arch = "x86")]
 use core::arch::x86;
@@ -148,6 +304,12 @@ unsafe impl StableLayout for x86::__m256i {}
 unsafe impl StableLayout for x86::__m256 {}
 #[cfg(target_arch = "x86")]
 unsafe impl StableLayout for x86::__m256d {}
+#[cfg(target_arch = "x86")]
+unsafe impl StableLayout for x86::__m512i {}
+#[cfg(target_arch = "x86")]
+unsafe impl StableLayout for x86::__m512 {}
+#[cfg(target_arch = "x86")]
+unsafe impl StableLayout for x86::__m512d {}
 
 #[cfg(target_arch = "x86_64")]
 use core::arch::x86_64;
@@ -163,3 +325,448 @@ unsafe impl StableLayout for x86_64::__m256i {}
 unsafe impl StableLayout for x86_64::__m256 {}
 #[cfg(target_arch = "x86_64")]
 unsafe impl StableLayout for x86_64::__m256d {}
+#[cfg(target_arch = "x86_64")]
+unsafe impl StableLayout for x86_64::__m512i {}
+#[cfg(target_arch = "x86_64")]
+unsafe impl StableLayout for x86_64::__m512 {}
+#[cfg(target_arch = "x86_64")]
+unsafe impl StableLayout for x86_64::__m512d {}
+
+#[cfg(target_arch = "wasm32")]
+use core::arch::wasm32;
+#[cfg(target_arch = "wasm32")]
+unsafe impl StableLayout for wasm32::v128 {}
+
+// The NEON vector types are plain vectors, and the `x2`, `x3`, and `x4` types
+// are `repr(C)` structs of those vectors. The 16-bit float types are left out
+// because they're still unstable.
+#[allow(unused_macros)]
+macro_rules! impl_unsafe_marker_for_neon {
+  ( $marker:ident, $arch:ident ) => {
+    unsafe impl $marker for $arch::int8x8_t {}
+    unsafe impl $marker for $arch::int8x8x2_t {}
+    unsafe impl $marker for $arch::int8x8x3_t {}
+    unsafe impl $marker for $arch::int8x8x4_t {}
+    unsafe impl $marker for $arch::int8x16_t {}
+    unsafe impl $marker for $arch::int8x16x2_t {}
+    unsafe impl $marker for $arch::int8x16x3_t {}
+    unsafe impl $marker for $arch::int8x16x4_t {}
+    unsafe impl $marker for $arch::int16x4_t {}
+    unsafe impl $marker for $arch::int16x4x2_t {}
+    unsafe impl $marker for $arch::int16x4x3_t {}
+    unsafe impl $marker for $arch::int16x4x4_t {}
+    unsafe impl $marker for $arch::int16x8_t {}
+    unsafe impl $marker for $arch::int16x8x2_t {}
+    unsafe impl $marker for $arch::int16x8x3_t {}
+    unsafe impl $marker for $arch::int16x8x4_t {}
+    unsafe impl $marker for $arch::int32x2_t {}
+    unsafe impl $marker for $arch::int32x2x2_t {}
+    unsafe impl $marker for $arch::int32x2x3_t {}
+    unsafe impl $marker for $arch::int32x2x4_t {}
+    unsafe impl $marker for $arch::int32x4_t {}
+    unsafe impl $marker for $arch::int32x4x2_t {}
+    unsafe impl $marker for $arch::int32x4x3_t {}
+    unsafe impl $marker for $arch::int32x4x4_t {}
+    unsafe impl $marker for $arch::int64x1_t {}
+    unsafe impl $marker for $arch::int64x1x2_t {}
+    unsafe impl $marker for $arch::int64x1x3_t {}
+    unsafe impl $marker for $arch::int64x1x4_t {}
+    unsafe impl $marker for $arch::int64x2_t {}
+    unsafe impl $marker for $arch::int64x2x2_t {}
+    unsafe impl $marker for $arch::int64x2x3_t {}
+    unsafe impl $marker for $arch::int64x2x4_t {}
+    unsafe impl $marker for $arch::uint8x8_t {}
+    unsafe impl $marker for $arch::uint8x8x2_t {}
+    unsafe impl $marker for $arch::uint8x8x3_t {}
+    unsafe impl $marker for $arch::uint8x8x4_t {}
+    unsafe impl $marker for $arch::uint8x16_t {}
+    unsafe impl $marker for $arch::uint8x16x2_t {}
+    unsafe impl $marker for $arch::uint8x16x3_t {}
+    unsafe impl $marker for $arch::uint8x16x4_t {}
+    unsafe impl $marker for $arch::uint16x4_t {}
+    unsafe impl $marker for $arch::uint16x4x2_t {}
+    unsafe impl $marker for $arch::uint16x4x3_t {}
+    unsafe impl $marker for $arch::uint16x4x4_t {}
+    unsafe impl $marker for $arch::uint16x8_t {}
+    unsafe impl $marker for $arch::uint16x8x2_t {}
+    unsafe impl $marker for $arch::uint16x8x3_t {}
+    unsafe impl $marker for $arch::uint16x8x4_t {}
+    unsafe impl $marker for $arch::uint32x2_t {}
+    unsafe impl $marker for $arch::uint32x2x2_t {}
+    unsafe impl $marker for $arch::uint32x2x3_t {}
+    unsafe impl $marker for $arch::uint32x2x4_t {}
+    unsafe impl $marker for $arch::uint32x4_t {}
+    unsafe impl $marker for $arch::uint32x4x2_t {}
+    unsafe impl $marker for $arch::uint32x4x3_t {}
+    unsafe impl $marker for $arch::uint32x4x4_t {}
+    unsafe impl $marker for $arch::uint64x1_t {}
+    unsafe impl $marker for $arch::uint64x1x2_t {}
+    unsafe impl $marker for $arch::uint64x1x3_t {}
+    unsafe impl $marker for $arch::uint64x1x4_t {}
+    unsafe impl $marker for $arch::uint64x2_t {}
+    unsafe impl $marker for $arch::uint64x2x2_t {}
+    unsafe impl $marker for $arch::uint64x2x3_t {}
+    unsafe impl $marker for $arch::uint64x2x4_t {}
+    unsafe impl $marker for $arch::float32x2_t {}
+    unsafe impl $marker for $arch::float32x2x2_t {}
+    unsafe impl $marker for $arch::float32x2x3_t {}
+    unsafe impl $marker for $arch::float32x2x4_t {}
+    unsafe impl $marker for $arch::float32x4_t {}
+    unsafe impl $marker for $arch::float32x4x2_t {}
+    unsafe impl $marker for $arch::float32x4x3_t {}
+    unsafe impl $marker for $arch::float32x4x4_t {}
+    unsafe impl $marker for $arch::poly8x8_t {}
+    unsafe impl $marker for $arch::poly8x8x2_t {}
+    unsafe impl $marker for $arch::poly8x8x3_t {}
+    unsafe impl $marker for $arch::poly8x8x4_t {}
+    unsafe impl $marker for $arch::poly8x16_t {}
+    unsafe impl $marker for $arch::poly8x16x2_t {}
+    unsafe impl $marker for $arch::poly8x16x3_t {}
+    unsafe impl $marker for $arch::poly8x16x4_t {}
+    unsafe impl $marker for $arch::poly16x4_t {}
+    unsafe impl $marker for $arch::poly16x4x2_t {}
+    unsafe impl $marker for $arch::poly16x4x3_t {}
+    unsafe impl $marker for $arch::poly16x4x4_t {}
+    unsafe impl $marker for $arch::poly16x8_t {}
+    unsafe impl $marker for $arch::poly16x8x2_t {}
+    unsafe impl $marker for $arch::poly16x8x3_t {}
+    unsafe impl $marker for $arch::poly16x8x4_t {}
+    unsafe impl $marker for $arch::poly64x1_t {}
+    unsafe impl $marker for $arch::poly64x1x2_t {}
+    unsafe impl $marker for $arch::poly64x1x3_t {}
+    unsafe impl $marker for $arch::poly64x1x4_t {}
+    unsafe impl $marker for $arch::poly64x2_t {}
+    unsafe impl $marker for $arch::poly64x2x2_t {}
+    unsafe impl $marker for $arch::poly64x2x3_t {}
+    unsafe impl $marker for $arch::poly64x2x4_t {}
+  };
+}
+
+#[cfg(target_arch = "aarch64")]
+use core::arch::aarch64;
+#[cfg(target_arch = "aarch64")]
+impl_unsafe_marker_for_neon!(StableLayout, aarch64);
+#[cfg(target_arch = "aarch64")]
+unsafe impl StableLayout for aarch64::float64x1_t {}
+#[cfg(target_arch = "aarch64")]
+unsafe impl StableLayout for aarch64::float64x1x2_t {}
+#[cfg(target_arch = "aarch64")]
+unsafe impl StableLayout for aarch64::float64x1x3_t {}
+#[cfg(target_arch = "aarch64")]
+unsafe impl StableLayout for aarch64::float64x1x4_t {}
+#[cfg(target_arch = "aarch64")]
+unsafe impl StableLayout for aarch64::float64x2_t {}
+#[cfg(target_arch = "aarch64")]
+unsafe impl StableLayout for aarch64::float64x2x2_t {}
+#[cfg(target_arch = "aarch64")]
+unsafe impl StableLayout for aarch64::float64x2x3_t {}
+#[cfg(target_arch = "aarch64")]
+unsafe impl StableLayout for aarch64::float64x2x4_t {}
+
+#[cfg(all(
+  feature = "nightly_arm_neon",
+  target_arch = "arm",
+  target_feature = "v7"
+))]
+use core::arch::arm;
+#[cfg(all(
+  feature = "nightly_arm_neon",
+  target_arch = "arm",
+  target_feature = "v7"
+))]
+impl_unsafe_marker_for_neon!(StableLayout, arm);
+
+// Most of the impls from here on are for types from other crates, each behind
+// the feature of the same name. Those types are all `repr(C)` or
+// `repr(transparent)` over fields that are `StableLayout` themselves, and any
+// other type parameters (units, tags, and so on) only appear in `PhantomData`.
+
+// The statically sized matrices (which includes the statically sized vectors)
+// are just a `[[T; R]; C]` in disguise.
+#[cfg(feature = "nalgebra")]
+use nalgebra::{
+  Point, Quaternion, Rotation, SMatrix, Scalar, Translation, Unit,
+};
+#[cfg(feature = "nalgebra")]
+unsafe impl<T, const R: usize, const C: usize> StableLayout for SMatrix<T, R, C> where
+  T: StableLayout
+{
+}
+#[cfg(feature = "nalgebra")]
+unsafe impl<T, const D: usize> StableLayout for Point<T, D> where
+  T: Scalar + StableLayout
+{
+}
+#[cfg(feature = "nalgebra")]
+unsafe impl<T> StableLayout for Quaternion<T> where T: StableLayout {}
+#[cfg(feature = "nalgebra")]
+unsafe impl<T, const D: usize> StableLayout for Translation<T, D> where
+  T: StableLayout
+{
+}
+#[cfg(feature = "nalgebra")]
+unsafe impl<T, const D: usize> StableLayout for Rotation<T, D> where
+  T: StableLayout
+{
+}
+#[cfg(feature = "nalgebra")]
+unsafe impl<T> StableLayout for Unit<T> where T: StableLayout {}
+
+#[cfg(feature = "rgb")]
+use rgb::{Abgr, Argb, Bgr, Bgra, Gray, GrayAlpha, Grb, Rgb, Rgba};
+#[cfg(feature = "rgb")]
+unsafe impl<T> StableLayout for Rgb<T> where T: StableLayout {}
+#[cfg(feature = "rgb")]
+unsafe impl<T, A> StableLayout for Rgba<T, A>
+where
+  T: StableLayout,
+  A: StableLayout,
+{
+}
+#[cfg(feature = "rgb")]
+unsafe impl<T, A> StableLayout for Argb<T, A>
+where
+  T: StableLayout,
+  A: StableLayout,
+{
+}
+#[cfg(feature = "rgb")]
+unsafe impl<T> StableLayout for Bgr<T> where T: StableLayout {}
+#[cfg(feature = "rgb")]
+unsafe impl<T, A> StableLayout for Bgra<T, A>
+where
+  T: StableLayout,
+  A: StableLayout,
+{
+}
+#[cfg(feature = "rgb")]
+unsafe impl<T, A> StableLayout for Abgr<T, A>
+where
+  T: StableLayout,
+  A: StableLayout,
+{
+}
+#[cfg(feature = "rgb")]
+unsafe impl<T> StableLayout for Grb<T> where T: StableLayout {}
+#[cfg(feature = "rgb")]
+unsafe impl<T> StableLayout for Gray<T> where T: StableLayout {}
+#[cfg(feature = "rgb")]
+unsafe impl<T, A> StableLayout for GrayAlpha<T, A>
+where
+  T: StableLayout,
+  A: StableLayout,
+{
+}
+
+#[cfg(feature = "fixed")]
+use fixed::{
+  FixedI16, FixedI32, FixedI64, FixedI8, FixedU16, FixedU32, FixedU64, FixedU8,
+};
+#[cfg(feature = "fixed")]
+unsafe impl<Frac> StableLayout for FixedI8<Frac> {}
+#[cfg(feature = "fixed")]
+unsafe impl<Frac> StableLayout for FixedI16<Frac> {}
+#[cfg(feature = "fixed")]
+unsafe impl<Frac> StableLayout for FixedI32<Frac> {}
+#[cfg(feature = "fixed")]
+unsafe impl<Frac> StableLayout for FixedI64<Frac> {}
+#[cfg(feature = "fixed")]
+unsafe impl<Frac> StableLayout for FixedU8<Frac> {}
+#[cfg(feature = "fixed")]
+unsafe impl<Frac> StableLayout for FixedU16<Frac> {}
+#[cfg(feature = "fixed")]
+unsafe impl<Frac> StableLayout for FixedU32<Frac> {}
+#[cfg(feature = "fixed")]
+unsafe impl<Frac> StableLayout for FixedU64<Frac> {}
+#[cfg(all(feature = "fixed", feature = "int128"))]
+unsafe impl<Frac> StableLayout for fixed::FixedI128<Frac> {}
+#[cfg(all(feature = "fixed", feature = "int128"))]
+unsafe impl<Frac> StableLayout for fixed::FixedU128<Frac> {}
+
+#[cfg(feature = "ordered-float")]
+use ordered_float::{NotNan, OrderedFloat};
+#[cfg(feature = "ordered-float")]
+unsafe impl<T> StableLayout for OrderedFloat<T> where T: StableLayout {}
+#[cfg(feature = "ordered-float")]
+unsafe impl<T> StableLayout for NotNan<T> where T: StableLayout {}
+
+// `Simd<T, N>` is documented to have the same "shape" as `[T; N]`, but the
+// alignment is only specified as being "based on both T and N". We only cover
+// the power of two lane counts, since that's where the alignment is simply the
+// size of the whole vector, same as the `core::arch` types.
+#[cfg(feature = "nightly_simd")]
+use core::simd::{Simd, SimdElement};
+#[cfg(feature = "nightly_simd")]
+macro_rules! impl_unsafe_marker_for_simd {
+  ( $marker:ident , $( $n:expr ),* ) => {
+    $(unsafe impl<T> $marker for Simd<T, $n> where T: SimdElement + $marker {})*
+  }
+}
+#[cfg(feature = "nightly_simd")]
+impl_unsafe_marker_for_simd!(StableLayout, 1, 2, 4, 8, 16, 32, 64);
+
+#[cfg(feature = "euclid")]
+use euclid::{
+  Box2D, Point2D, Point3D, Rect, Size2D, Size3D, Transform2D, Transform3D,
+  Vector2D, Vector3D,
+};
+#[cfg(feature = "euclid")]
+unsafe impl<T, U> StableLayout for Point2D<T, U> where T: StableLayout {}
+#[cfg(feature = "euclid")]
+unsafe impl<T, U> StableLayout for Point3D<T, U> where T: StableLayout {}
+#[cfg(feature = "euclid")]
+unsafe impl<T, U> StableLayout for Vector2D<T, U> where T: StableLayout {}
+#[cfg(feature = "euclid")]
+unsafe impl<T, U> StableLayout for Vector3D<T, U> where T: StableLayout {}
+#[cfg(feature = "euclid")]
+unsafe impl<T, U> StableLayout for Size2D<T, U> where T: StableLayout {}
+#[cfg(feature = "euclid")]
+unsafe impl<T, U> StableLayout for Size3D<T, U> where T: StableLayout {}
+#[cfg(feature = "euclid")]
+unsafe impl<T, U> StableLayout for Rect<T, U> where T: StableLayout {}
+#[cfg(feature = "euclid")]
+unsafe impl<T, U> StableLayout for Box2D<T, U> where T: StableLayout {}
+#[cfg(feature = "euclid")]
+unsafe impl<T, Src, Dst> StableLayout for Transform2D<T, Src, Dst> where
+  T: StableLayout
+{
+}
+#[cfg(feature = "euclid")]
+unsafe impl<T, Src, Dst> StableLayout for Transform3D<T, Src, Dst> where
+  T: StableLayout
+{
+}
+
+// Only the common `libc` structs that exist on every Unix target are covered.
+#[cfg(all(feature = "libc", unix))]
+use libc::{
+  in6_addr, in_addr, iovec, pollfd, sockaddr, sockaddr_in, sockaddr_in6,
+  sockaddr_storage, sockaddr_un, stat, timespec, timeval, tm,
+};
+#[cfg(all(feature = "libc", unix))]
+unsafe impl StableLayout for timespec {}
+#[cfg(all(feature = "libc", unix))]
+unsafe impl StableLayout for timeval {}
+#[cfg(all(feature = "libc", unix))]
+unsafe impl StableLayout for tm {}
+#[cfg(all(feature = "libc", unix))]
+unsafe impl StableLayout for iovec {}
+#[cfg(all(feature = "libc", unix))]
+unsafe impl StableLayout for pollfd {}
+#[cfg(all(feature = "libc", unix))]
+unsafe impl StableLayout for stat {}
+#[cfg(all(feature = "libc", unix))]
+unsafe impl StableLayout for sockaddr {}
+#[cfg(all(feature = "libc", unix))]
+unsafe impl StableLayout for sockaddr_storage {}
+#[cfg(all(feature = "libc", unix))]
+unsafe impl StableLayout for sockaddr_in {}
+#[cfg(all(feature = "libc", unix))]
+unsafe impl StableLayout for sockaddr_in6 {}
+#[cfg(all(feature = "libc", unix))]
+unsafe impl StableLayout for sockaddr_un {}
+#[cfg(all(feature = "libc", unix))]
+unsafe impl StableLayout for in_addr {}
+#[cfg(all(feature = "libc", unix))]
+unsafe impl StableLayout for in6_addr {}
+
+#[cfg(feature = "windows-sys")]
+use windows_sys::{
+  core::GUID,
+  Win32::{
+    Foundation::{FILETIME, LUID, POINT, RECT, SIZE, SYSTEMTIME},
+    Security::SECURITY_ATTRIBUTES,
+    System::IO::OVERLAPPED,
+  },
+};
+#[cfg(feature = "windows-sys")]
+unsafe impl StableLayout for GUID {}
+#[cfg(feature = "windows-sys")]
+unsafe impl StableLayout for FILETIME {}
+#[cfg(feature = "windows-sys")]
+unsafe impl StableLayout for SYSTEMTIME {}
+#[cfg(feature = "windows-sys")]
+unsafe impl StableLayout for LUID {}
+#[cfg(feature = "windows-sys")]
+unsafe impl StableLayout for POINT {}
+#[cfg(feature = "windows-sys")]
+unsafe impl StableLayout for SIZE {}
+#[cfg(feature = "windows-sys")]
+unsafe impl StableLayout for RECT {}
+#[cfg(feature = "windows-sys")]
+unsafe impl StableLayout for SECURITY_ATTRIBUTES {}
+#[cfg(feature = "windows-sys")]
+unsafe impl StableLayout for OVERLAPPED {}
+
+// The `half` types are named by path, since importing `f16` would shadow the
+// primitive type.
+#[cfg(feature = "half")]
+unsafe impl StableLayout for half::f16 {}
+#[cfg(feature = "half")]
+unsafe impl StableLayout for half::bf16 {}
+
+#[cfg(feature = "uuid")]
+unsafe impl StableLayout for uuid::Uuid {}
+
+// The `glam` types that use SIMD keep the same size and alignment even when
+// SIMD isn't available, so the layout doesn't depend on the target.
+#[cfg(feature = "glam")]
+use glam::{
+  Affine2, Affine3, Affine3A, DAffine2, DAffine3, DMat2, DMat3, DMat4, DQuat,
+  DVec2, DVec3, DVec4, IVec2, IVec3, IVec4, Mat2, Mat3, Mat3A, Mat4, Quat,
+  UVec2, UVec3, UVec4, Vec2, Vec3, Vec3A, Vec4,
+};
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for Vec2 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for Vec3 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for Vec3A {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for Vec4 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for Mat2 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for Mat3 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for Mat3A {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for Mat4 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for Quat {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for Affine2 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for Affine3 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for Affine3A {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for DVec2 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for DVec3 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for DVec4 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for DMat2 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for DMat3 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for DMat4 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for DQuat {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for DAffine2 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for DAffine3 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for IVec2 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for IVec3 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for IVec4 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for UVec2 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for UVec3 {}
+#[cfg(feature = "glam")]
+unsafe impl StableLayout for UVec4 {}
@@ -0,0 +1,187 @@
+#![cfg(all(feature = "log", feature = "std"))]
+
+use core::{ffi::c_void, fmt::Debug};
+use std::{boxed::Box, string::ToString};
+
+use super::{SharedStr, StableLayout};
+
+/// A log record with a stable layout.
+///
+/// This is what a [`StableLogger`] passes to its callback. The `level` uses
+/// the same numbering as `log::Level`: 1 is `Error`, 2 is `Warn`, 3 is `Info`,
+/// 4 is `Debug`, and 5 is `Trace`.
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StableLogRecord<'a>`
+/// typedef struct {
+///   uint32_t level;
+///   SharedStr target;
+///   SharedStr message;
+/// } StableLogRecord;
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct StableLogRecord<'a> {
+  /// The level, from 1 (`Error`) to 5 (`Trace`).
+  pub level: u32,
+  /// The target of the record, usually the module path.
+  pub target: SharedStr<'a>,
+  /// The formatted message.
+  pub message: SharedStr<'a>,
+}
+
+unsafe impl<'a> StableLayout for StableLogRecord<'a> {}
+
+impl<'a> StableLogRecord<'a> {
+  /// The level as a `log::Level`, or `None` if it's out of range.
+  #[inline]
+  pub fn log_level(&self) -> Option<log::Level> {
+    match self.level {
+      1 => Some(log::Level::Error),
+      2 => Some(log::Level::Warn),
+      3 => Some(log::Level::Info),
+      4 => Some(log::Level::Debug),
+      5 => Some(log::Level::Trace),
+      _ => None,
+    }
+  }
+
+  /// Logs the record with this side's logger.
+  ///
+  /// This is what a host's callback usually does with the records that it
+  /// gets. Records with an out of range level are ignored.
+  #[inline]
+  pub fn log(&self) {
+    if let Some(level) = self.log_level() {
+      log::logger().log(
+        &log::Record::builder()
+          .level(level)
+          .target(&self.target)
+          .args(format_args!("{}", &*self.message))
+          .build(),
+      );
+    }
+  }
+}
+
+/// The callback that a [`StableLogger`] forwards records to.
+///
+/// The first argument is the context pointer that the logger was made with.
+pub type StableLogFn =
+  for<'a> unsafe extern "C" fn(*mut c_void, StableLogRecord<'a>);
+
+/// A `log::Log` implementation that forwards records across FFI.
+///
+/// A plugin makes one of these with a callback (and context pointer) from the
+/// host, then installs it as the plugin's logger. Every record logged inside
+/// the plugin is then formatted and passed to the callback as a
+/// [`StableLogRecord`], and the host can log it with its own logger. The two
+/// sides don't need to share any Rust types, or even the same version of
+/// `log`.
+///
+/// This type itself is _not_ `repr(C)`, it lives on the plugin side.
+///
+/// ```rust
+/// # use chromium::*;
+/// # use core::ffi::c_void;
+/// # use std::sync::Mutex;
+/// static SEEN: Mutex<Vec<(u32, String, String)>> = Mutex::new(Vec::new());
+/// // This would be in the host.
+/// unsafe extern "C" fn host_log(_: *mut c_void, rec: StableLogRecord<'_>) {
+///   let entry = (rec.level, rec.target.to_string(), rec.message.to_string());
+///   SEEN.lock().unwrap().push(entry);
+/// }
+/// // And this would be in the plugin.
+/// let logger = unsafe {
+///   StableLogger::new(host_log, core::ptr::null_mut(), log::LevelFilter::Info)
+/// };
+/// use log::Log;
+/// let record = log::Record::builder()
+///   .level(log::Level::Warn)
+///   .target("plugin")
+///   .args(format_args!("{} left", 3))
+///   .build();
+/// logger.log(&record);
+/// let record = log::Record::builder().level(log::Level::Debug).build();
+/// logger.log(&record);
+/// let seen = SEEN.lock().unwrap();
+/// assert_eq!(&seen[..], &[(2, "plugin".to_string(), "3 left".to_string())]);
+/// ```
+pub struct StableLogger {
+  callback: StableLogFn,
+  context: *mut c_void,
+  max_level: log::LevelFilter,
+}
+
+// Safety: `new` requires that the callback be callable from any thread with
+// the context pointer.
+unsafe impl Send for StableLogger {}
+unsafe impl Sync for StableLogger {}
+
+impl StableLogger {
+  /// Makes a logger that forwards records at or below `max_level` to
+  /// `callback`.
+  ///
+  /// ## Safety
+  /// * `callback` must be safe to call with `context` from any thread, at any
+  ///   time for as long as the logger exists.
+  #[inline]
+  pub const unsafe fn new(
+    callback: StableLogFn, context: *mut c_void, max_level: log::LevelFilter,
+  ) -> Self {
+    Self { callback, context, max_level }
+  }
+
+  /// Installs this as the global logger, and sets the global max level to
+  /// match.
+  ///
+  /// This fails if a global logger is already set.
+  #[inline]
+  pub fn install(self) -> Result<(), log::SetLoggerError> {
+    let max_level = self.max_level;
+    log::set_boxed_logger(Box::new(self))?;
+    log::set_max_level(max_level);
+    Ok(())
+  }
+}
+
+impl Debug for StableLogger {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_struct("StableLogger")
+      .field("context", &self.context)
+      .field("max_level", &self.max_level)
+      .finish()
+  }
+}
+
+impl log::Log for StableLogger {
+  #[inline]
+  fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
+    metadata.level() <= self.max_level
+  }
+
+  fn log(&self, record: &log::Record<'_>) {
+    if !self.enabled(record.metadata()) {
+      return;
+    }
+    let formatted;
+    let message = match record.args().as_str() {
+      Some(s) => s,
+      None => {
+        formatted = record.args().to_string();
+        &formatted
+      }
+    };
+    let stable = StableLogRecord {
+      level: record.level() as u32,
+      target: SharedStr::from(record.target()),
+      message: SharedStr::from(message),
+    };
+    // Safety: See the requirements of `new`.
+    unsafe { (self.callback)(self.context, stable) }
+  }
+
+  #[inline]
+  fn flush(&self) {}
+}
@@ -0,0 +1,200 @@
+use core::{fmt::Debug, mem::MaybeUninit};
+
+use super::StableLayout;
+
+// General Safety Note: The soundness of the `StableOption` type is centered
+// around the fact that the fields are all private, and so *safe rust* must
+// construct values of the type from an existing `Option`. However, because the
+// type is `repr(C)` it can of course be constructed with unsafe rust, or even
+// by foreign code. It is the responsibility of _the other code_ to ensure that
+// the tag is correct for the payload.
+
+/// A struct for optional values with a stable layout.
+///
+/// This is a `repr(C)` variant of `Option<T>`. A few `Option` types (such as
+/// `Option<&T>` and `Option<NonZeroU32>`) already have a stable layout thanks
+/// to the null pointer optimization, but for most `T` the layout of an
+/// `Option<T>` is unspecified.
+///
+/// Like the other owned types in this crate, dropping a `StableOption` doesn't
+/// drop the payload. Turn it back into an `Option` to do that.
+///
+/// ## Unsafety
+///
+/// Because this type is primarily intended to help _unsafe_ Rust we should
+/// discuss the precise guarantees offered:
+/// * **Validity Invariants**
+///   * The data layout is a `u8` tag and then a `T`.
+/// * **Soundness Invariants**
+///   * The tag must be 0 (none) or 1 (some).
+///   * If the tag is 1 then the `T` must be initialized.
+///
+/// If your payload type is compatible with the C ABI, such as `u32`, then that
+/// `StableOption` is equivalent to the following C declaration:
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StableOption<u32>`
+/// typedef struct {
+///   uint8_t tag;
+///   uint32_t value;
+/// } StableOption_u32;
+/// ```
+#[repr(C)]
+pub struct StableOption<T>
+where
+  T: StableLayout,
+{
+  tag: u8,
+  value: MaybeUninit<T>,
+}
+
+unsafe impl<T> StableLayout for StableOption<T> where T: StableLayout {}
+
+impl<T> StableOption<T>
+where
+  T: StableLayout,
+{
+  /// The value of the tag when there's no payload.
+  pub const TAG_NONE: u8 = 0;
+  /// The value of the tag when there's a payload.
+  pub const TAG_SOME: u8 = 1;
+
+  /// An option with no payload.
+  #[inline(always)]
+  pub const fn new_none() -> Self {
+    Self { tag: Self::TAG_NONE, value: MaybeUninit::uninit() }
+  }
+
+  /// An option with a payload.
+  #[inline(always)]
+  pub const fn new_some(value: T) -> Self {
+    Self { tag: Self::TAG_SOME, value: MaybeUninit::new(value) }
+  }
+
+  /// If there's a payload.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// assert!(StableOption::new_some(5_u32).is_some());
+  /// assert!(StableOption::<u32>::new_none().is_none());
+  /// ```
+  #[inline(always)]
+  pub fn is_some(&self) -> bool {
+    self.tag == Self::TAG_SOME
+  }
+
+  /// If there's no payload.
+  #[inline(always)]
+  pub fn is_none(&self) -> bool {
+    !self.is_some()
+  }
+
+  /// Borrows the payload, if there is one.
+  #[inline]
+  pub fn as_ref(&self) -> Option<&T> {
+    if self.is_some() {
+      // Safety: See note at the top of the module.
+      Some(unsafe { &*self.value.as_ptr() })
+    } else {
+      None
+    }
+  }
+
+  /// Mutably borrows the payload, if there is one.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let mut opt = StableOption::new_some(5_u32);
+  /// *opt.as_mut().unwrap() += 1;
+  /// assert_eq!(opt.as_ref(), Some(&6));
+  /// ```
+  #[inline]
+  pub fn as_mut(&mut self) -> Option<&mut T> {
+    if self.is_some() {
+      // Safety: See note at the top of the module.
+      Some(unsafe { &mut *self.value.as_mut_ptr() })
+    } else {
+      None
+    }
+  }
+
+  /// Takes the payload out, leaving no payload in its place.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let mut opt = StableOption::new_some(5_u32);
+  /// assert_eq!(opt.take(), Some(5));
+  /// assert!(opt.is_none());
+  /// ```
+  #[inline]
+  pub fn take(&mut self) -> Option<T> {
+    Option::from(core::mem::take(self))
+  }
+}
+
+impl<T> Clone for StableOption<T>
+where
+  T: StableLayout + Clone,
+{
+  #[inline]
+  fn clone(&self) -> Self {
+    Self::from(self.as_ref().cloned())
+  }
+}
+
+impl<T> Copy for StableOption<T> where T: StableLayout + Copy {}
+
+impl<T> Debug for StableOption<T>
+where
+  T: StableLayout + Debug,
+{
+  /// Debug prints as an `Option` would.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    Debug::fmt(&self.as_ref(), f)
+  }
+}
+
+impl<T> Default for StableOption<T>
+where
+  T: StableLayout,
+{
+  /// Defaults to no payload.
+  #[inline(always)]
+  fn default() -> Self {
+    Self::new_none()
+  }
+}
+
+impl<T> From<Option<T>> for StableOption<T>
+where
+  T: StableLayout,
+{
+  #[inline]
+  fn from(opt: Option<T>) -> Self {
+    match opt {
+      Some(value) => Self::new_some(value),
+      None => Self::new_none(),
+    }
+  }
+}
+
+impl<T> From<StableOption<T>> for Option<T>
+where
+  T: StableLayout,
+{
+  /// ```rust
+  /// # use chromium::*;
+  /// let opt = StableOption::from(Some(String::from("hi").len()));
+  /// assert_eq!(Option::from(opt), Some(2));
+  /// ```
+  #[inline]
+  fn from(opt: StableOption<T>) -> Self {
+    if opt.is_some() {
+      // Safety: See note at the top of the module.
+      Some(unsafe { opt.value.as_ptr().read() })
+    } else {
+      None
+    }
+  }
+}
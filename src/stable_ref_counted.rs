@@ -0,0 +1,169 @@
+#![cfg(target_has_atomic = "ptr")]
+
+use core::{
+  fmt::Debug,
+  sync::atomic::{fence, AtomicUsize, Ordering},
+};
+
+use super::StableLayout;
+
+#[cfg(feature = "unsafe_alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "unsafe_alloc")]
+use core::ptr::NonNull;
+
+/// A reference counted value with a stable layout.
+///
+/// This is the header and payload of an intrusively reference counted object:
+/// an atomic count, the function that destroys the object once the count hits
+/// zero, and then the payload itself. Put one of these at the start of your
+/// FFI object, pass pointers to it around, and have each side call
+/// [`acquire`](Self::acquire) and [`release`](Self::release) as they gain and
+/// give up references.
+///
+/// Both helpers are `extern "C"`, so you can hand C code function pointers to
+/// them (or to wrappers with your own `#[no_mangle]` names, since generic
+/// functions can't have fixed symbol names).
+///
+/// ## Unsafety
+///
+/// Because this type is primarily intended to help _unsafe_ Rust we should
+/// discuss the precise guarantees offered:
+/// * **Validity Invariants**
+///   * The data layout is an `AtomicUsize` (the same layout as `usize`), then
+///     an `unsafe extern "C" fn(*mut StableRefCounted<T>)`, then a `T`.
+/// * **Soundness Invariants**
+///   * The count must be the number of live references to the object.
+///   * The drop function must be safe to call exactly once, with a pointer to
+///     this object, once the count reaches 0.
+///
+/// If your payload were a `u64` then it would be equivalent layout to the
+/// following C declaration:
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StableRefCounted<u64>`
+/// typedef struct StableRefCounted_u64 {
+///   uintptr_t count; // always access atomically!
+///   void (*drop_fn)(struct StableRefCounted_u64 *);
+///   uint64_t payload;
+/// } StableRefCounted_u64;
+/// ```
+#[repr(C)]
+pub struct StableRefCounted<T>
+where
+  T: StableLayout,
+{
+  count: AtomicUsize,
+  drop_fn: unsafe extern "C" fn(*mut StableRefCounted<T>),
+  payload: T,
+}
+
+unsafe impl<T: StableLayout> StableLayout for StableRefCounted<T> {}
+
+impl<T> StableRefCounted<T>
+where
+  T: StableLayout,
+{
+  /// Makes a new object with a count of 1 and the drop function given.
+  ///
+  /// This is for when you manage the storage yourself. The drop function will
+  /// be called with a pointer to wherever the value ends up living, so that
+  /// has to be somewhere that the drop function knows how to clean up.
+  #[inline]
+  pub const fn with_drop_fn(
+    payload: T, drop_fn: unsafe extern "C" fn(*mut StableRefCounted<T>),
+  ) -> Self {
+    Self { count: AtomicUsize::new(1), drop_fn, payload }
+  }
+
+  /// Puts a new object on the heap with a count of 1.
+  ///
+  /// The drop function frees the heap allocation after dropping the payload.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let p = StableRefCounted::new_boxed(7_u32).as_ptr();
+  /// unsafe {
+  ///   StableRefCounted::acquire(p);
+  ///   assert_eq!((*p).count(), 2);
+  ///   assert_eq!(*(*p).payload(), 7);
+  ///   StableRefCounted::release(p);
+  ///   StableRefCounted::release(p); // freed here
+  /// }
+  /// ```
+  #[cfg(feature = "unsafe_alloc")]
+  #[inline]
+  pub fn new_boxed(payload: T) -> NonNull<Self> {
+    unsafe extern "C" fn drop_boxed<T: StableLayout>(
+      this: *mut StableRefCounted<T>,
+    ) {
+      drop(Box::from_raw(this))
+    }
+    let b = Box::new(Self::with_drop_fn(payload, drop_boxed::<T>));
+    // Safety: `Box::into_raw` is never null.
+    unsafe { NonNull::new_unchecked(Box::into_raw(b)) }
+  }
+
+  /// The payload.
+  #[inline(always)]
+  pub fn payload(&self) -> &T {
+    &self.payload
+  }
+
+  /// The current count.
+  ///
+  /// Other threads can change the count at any time, so this is only a
+  /// snapshot.
+  #[inline(always)]
+  pub fn count(&self) -> usize {
+    self.count.load(Ordering::Relaxed)
+  }
+
+  /// Adds one to the count.
+  ///
+  /// ## Panics
+  /// If the count goes above `isize::MAX`, the same as with `Arc`. Since this
+  /// is an `extern "C"` function, that aborts the process.
+  ///
+  /// ## Safety
+  /// `this` must point to a live object.
+  pub unsafe extern "C" fn acquire(this: *const Self) {
+    // Relaxed is enough here for the same reason that it's enough in
+    // `Arc::clone`: the caller already holds a reference, so the object can't
+    // go away while we add one.
+    let old = (*this).count.fetch_add(1, Ordering::Relaxed);
+    if old > isize::MAX as usize {
+      panic!("StableRefCounted count overflow");
+    }
+  }
+
+  /// Subtracts one from the count, calling the drop function if the count
+  /// reaches 0.
+  ///
+  /// ## Safety
+  /// `this` must point to a live object, and the caller gives up their
+  /// reference to it.
+  pub unsafe extern "C" fn release(this: *mut Self) {
+    if (*this).count.fetch_sub(1, Ordering::Release) != 1 {
+      return;
+    }
+    // This pairs with the `Release` above, the same as in `Arc::drop`, so that
+    // all uses of the object on other threads happen before the drop function
+    // runs.
+    fence(Ordering::Acquire);
+    ((*this).drop_fn)(this)
+  }
+}
+
+impl<T: Debug> Debug for StableRefCounted<T>
+where
+  T: StableLayout,
+{
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_struct("StableRefCounted")
+      .field("count", &self.count())
+      .field("payload", &self.payload)
+      .finish()
+  }
+}
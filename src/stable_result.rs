@@ -0,0 +1,359 @@
+use core::{fmt::Debug, mem::ManuallyDrop};
+
+use super::{StableLayout, StableStatus};
+
+// General Safety Note: The soundness of the `StableResult` type is centered
+// around the fact that the fields are all private, and so *safe rust* must
+// construct values of the type from an existing `Result`. However, because the
+// type is `repr(C)` it can of course be constructed with unsafe rust, or even
+// by foreign code. It is the responsibility of _the other code_ to ensure that
+// the tag is correct for the payload.
+
+#[repr(C)]
+union StableResultPayload<T, E> {
+  ok: ManuallyDrop<T>,
+  err: ManuallyDrop<E>,
+}
+
+impl<T: Copy, E: Copy> Clone for StableResultPayload<T, E> {
+  #[inline(always)]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T: Copy, E: Copy> Copy for StableResultPayload<T, E> {}
+
+/// A struct for fallible results with a stable layout.
+///
+/// This is a `repr(C)` variant of `Result<T, E>`, so that an `extern "C"`
+/// function can return a real result instead of a sentinel value.
+///
+/// Like the other owned types in this crate, dropping a `StableResult` doesn't
+/// drop the payload. Turn it back into a `Result` to do that.
+///
+/// With the `nightly_try` feature you can also use `?` on a `StableResult`,
+/// and use `?` inside a function that returns one.
+///
+/// ## Unsafety
+///
+/// Because this type is primarily intended to help _unsafe_ Rust we should
+/// discuss the precise guarantees offered:
+/// * **Validity Invariants**
+///   * The data layout is a `u8` tag and then a union of `T` and `E`.
+/// * **Soundness Invariants**
+///   * The tag must be 0 (ok) or 1 (err).
+///   * The union field that matches the tag must be initialized.
+///
+/// If your payload types are compatible with the C ABI then the `StableResult`
+/// is too. For example, `StableResult<u64, StableStatus>` is equivalent to the
+/// following C declaration:
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StableResult<u64, StableStatus>`
+/// typedef struct {
+///   uint8_t tag;
+///   union {
+///     uint64_t ok;
+///     int32_t err;
+///   } payload;
+/// } StableResult_u64_StableStatus;
+/// ```
+#[repr(C)]
+pub struct StableResult<T, E>
+where
+  T: StableLayout,
+  E: StableLayout,
+{
+  tag: u8,
+  payload: StableResultPayload<T, E>,
+}
+
+unsafe impl<T, E> StableLayout for StableResult<T, E>
+where
+  T: StableLayout,
+  E: StableLayout,
+{
+}
+
+impl<T, E> StableResult<T, E>
+where
+  T: StableLayout,
+  E: StableLayout,
+{
+  /// The value of the tag for a success.
+  pub const TAG_OK: u8 = 0;
+  /// The value of the tag for an error.
+  pub const TAG_ERR: u8 = 1;
+
+  /// A success.
+  #[inline(always)]
+  pub const fn new_ok(value: T) -> Self {
+    let ok = ManuallyDrop::new(value);
+    Self { tag: Self::TAG_OK, payload: StableResultPayload { ok } }
+  }
+
+  /// An error.
+  #[inline(always)]
+  pub const fn new_err(error: E) -> Self {
+    let err = ManuallyDrop::new(error);
+    Self { tag: Self::TAG_ERR, payload: StableResultPayload { err } }
+  }
+
+  /// If this is a success.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let r: StableResult<u32, StableStatus> = StableResult::new_ok(5);
+  /// assert!(r.is_ok());
+  /// assert!(!r.is_err());
+  /// ```
+  #[inline(always)]
+  pub fn is_ok(&self) -> bool {
+    self.tag == Self::TAG_OK
+  }
+
+  /// If this is an error.
+  #[inline(always)]
+  pub fn is_err(&self) -> bool {
+    !self.is_ok()
+  }
+
+  /// Borrows the payload.
+  #[inline]
+  pub fn as_ref(&self) -> Result<&T, &E> {
+    // Safety: See note at the top of the module.
+    unsafe {
+      if self.is_ok() {
+        Ok(&self.payload.ok)
+      } else {
+        Err(&self.payload.err)
+      }
+    }
+  }
+
+  /// Mutably borrows the payload.
+  #[inline]
+  pub fn as_mut(&mut self) -> Result<&mut T, &mut E> {
+    // Safety: See note at the top of the module.
+    unsafe {
+      if self.is_ok() {
+        Ok(&mut self.payload.ok)
+      } else {
+        Err(&mut self.payload.err)
+      }
+    }
+  }
+
+  /// Turns this back into a `Result`.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let r = StableResult::from(Err::<u32, _>(StableStatus::NOT_FOUND));
+  /// assert_eq!(r.into_result(), Err(StableStatus::NOT_FOUND));
+  /// ```
+  #[inline]
+  pub fn into_result(self) -> Result<T, E> {
+    let mut md = ManuallyDrop::new(self);
+    // Safety: See note at the top of the module. We're taking the payload
+    // out of a value that will never be used again.
+    unsafe {
+      if md.is_ok() {
+        Ok(ManuallyDrop::take(&mut md.payload.ok))
+      } else {
+        Err(ManuallyDrop::take(&mut md.payload.err))
+      }
+    }
+  }
+
+  /// The success value, if this is a success.
+  #[inline(always)]
+  pub fn ok(self) -> Option<T> {
+    self.into_result().ok()
+  }
+
+  /// The error value, if this is an error.
+  #[inline(always)]
+  pub fn err(self) -> Option<E> {
+    self.into_result().err()
+  }
+
+  /// Gives the success value.
+  ///
+  /// ## Panics
+  /// If this is an error.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let r: StableResult<u32, StableStatus> = StableResult::new_ok(5);
+  /// assert_eq!(r.unwrap(), 5);
+  /// ```
+  #[inline]
+  #[track_caller]
+  pub fn unwrap(self) -> T
+  where
+    E: Debug,
+  {
+    self.into_result().unwrap()
+  }
+
+  /// Gives the error value.
+  ///
+  /// ## Panics
+  /// If this is a success.
+  #[inline]
+  #[track_caller]
+  pub fn unwrap_err(self) -> E
+  where
+    T: Debug,
+  {
+    self.into_result().unwrap_err()
+  }
+}
+
+impl<T, E> Clone for StableResult<T, E>
+where
+  T: StableLayout + Clone,
+  E: StableLayout + Clone,
+{
+  #[inline]
+  fn clone(&self) -> Self {
+    match self.as_ref() {
+      Ok(t) => Self::new_ok(t.clone()),
+      Err(e) => Self::new_err(e.clone()),
+    }
+  }
+}
+
+impl<T, E> Copy for StableResult<T, E>
+where
+  T: StableLayout + Copy,
+  E: StableLayout + Copy,
+{
+}
+
+impl<T, E> Debug for StableResult<T, E>
+where
+  T: StableLayout + Debug,
+  E: StableLayout + Debug,
+{
+  /// Debug prints as a `Result` would.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    Debug::fmt(&self.as_ref(), f)
+  }
+}
+
+impl<T, E> From<Result<T, E>> for StableResult<T, E>
+where
+  T: StableLayout,
+  E: StableLayout,
+{
+  #[inline]
+  fn from(result: Result<T, E>) -> Self {
+    match result {
+      Ok(t) => Self::new_ok(t),
+      Err(e) => Self::new_err(e),
+    }
+  }
+}
+
+impl<T, E> From<StableResult<T, E>> for Result<T, E>
+where
+  T: StableLayout,
+  E: StableLayout,
+{
+  #[inline(always)]
+  fn from(result: StableResult<T, E>) -> Self {
+    result.into_result()
+  }
+}
+
+impl From<StableStatus> for StableResult<(), StableStatus> {
+  /// [`StableStatus::OK`] becomes a success, and any other code becomes an
+  /// error.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let r = StableResult::from(StableStatus::OK);
+  /// assert!(r.is_ok());
+  /// let r = StableResult::from(StableStatus::NOT_FOUND);
+  /// assert_eq!(r.err(), Some(StableStatus::NOT_FOUND));
+  /// let err = StableResult::new_err(StableStatus::TIMED_OUT);
+  /// assert_eq!(StableStatus::from(err), StableStatus::TIMED_OUT);
+  /// let ok = StableResult::new_ok(());
+  /// assert_eq!(StableStatus::from(ok), StableStatus::OK);
+  /// ```
+  #[inline]
+  fn from(status: StableStatus) -> Self {
+    Self::from(status.to_result())
+  }
+}
+
+impl From<StableResult<(), StableStatus>> for StableStatus {
+  /// A success becomes [`StableStatus::OK`], and an error becomes the error
+  /// code.
+  #[inline]
+  fn from(result: StableResult<(), StableStatus>) -> Self {
+    Self::from(result.into_result())
+  }
+}
+
+/// Lets `?` be used on a `StableResult`.
+///
+/// The residual is a plain `Result`, so `?` on a `StableResult` works in a
+/// function that returns either a `Result` or a `StableResult`.
+///
+/// ```rust
+/// # #![feature(try_trait_v2)]
+/// # use chromium::*;
+/// extern "C" fn parse(n: u32) -> StableResult<u32, StableStatus> {
+///   if n == 0 {
+///     return StableResult::new_err(StableStatus::INVALID_INPUT);
+///   }
+///   StableResult::new_ok(n * 2)
+/// }
+/// extern "C" fn twice(n: u32) -> StableResult<u32, StableStatus> {
+///   let x = parse(n)?;
+///   let y = parse(x)?;
+///   StableResult::new_ok(y)
+/// }
+/// assert_eq!(twice(3).into_result(), Ok(12));
+/// assert_eq!(twice(0).into_result(), Err(StableStatus::INVALID_INPUT));
+/// ```
+#[cfg(feature = "nightly_try")]
+impl<T, E> core::ops::Try for StableResult<T, E>
+where
+  T: StableLayout,
+  E: StableLayout,
+{
+  type Output = T;
+  type Residual = Result<core::convert::Infallible, E>;
+  #[inline]
+  fn from_output(output: T) -> Self {
+    Self::new_ok(output)
+  }
+  #[inline]
+  fn branch(self) -> core::ops::ControlFlow<Self::Residual, T> {
+    match self.into_result() {
+      Ok(t) => core::ops::ControlFlow::Continue(t),
+      Err(e) => core::ops::ControlFlow::Break(Err(e)),
+    }
+  }
+}
+
+#[cfg(feature = "nightly_try")]
+impl<T, E, F> core::ops::FromResidual<Result<core::convert::Infallible, F>>
+  for StableResult<T, E>
+where
+  T: StableLayout,
+  E: StableLayout + From<F>,
+{
+  #[inline]
+  fn from_residual(residual: Result<core::convert::Infallible, F>) -> Self {
+    match residual {
+      Err(f) => Self::new_err(E::from(f)),
+      Ok(never) => match never {},
+    }
+  }
+}
@@ -0,0 +1,204 @@
+#![cfg(feature = "unsafe_alloc")]
+
+use super::{SharedStr, StableLayout, StableString, StableVec};
+use alloc::{string::String, vec::Vec};
+use core::{fmt::Debug, marker::PhantomData, slice, str};
+
+// General Safety Note: The soundness of the `StableRope` type is centered
+// around the fact that the fields are all private, and so *safe rust* must
+// construct values of the type from existing valid strings. However, because
+// the type is `repr(C)` it can of course be constructed with unsafe rust, or
+// even by foreign code. It is the responsibility of _the other code_ to ensure
+// that the actual fields are valid.
+
+/// One piece of a [`StableRope`].
+///
+/// When `cap` is 0 this is a borrowed `SharedStr`, otherwise it's the parts of
+/// an owned `StableString`.
+#[repr(C)]
+struct RopeSegment<'a> {
+  ptr: *mut u8,
+  len: usize,
+  cap: usize,
+  life: PhantomData<&'a str>,
+}
+
+unsafe impl<'a> StableLayout for RopeSegment<'a> {}
+
+impl<'a> RopeSegment<'a> {
+  #[inline(always)]
+  fn as_str(&self) -> &str {
+    // Safety: See note at the top of the module.
+    unsafe {
+      str::from_utf8_unchecked(slice::from_raw_parts(self.ptr, self.len))
+    }
+  }
+}
+
+/// A string made of a list of borrowed and owned pieces, with a stable layout.
+///
+/// Appending to a rope never copies any string data, it just pushes another
+/// segment onto the list. When you're done building you can
+/// [`flatten`](StableRope::flatten) the rope into a single `StableString`,
+/// which copies everything exactly once.
+///
+/// ## Unsafety
+///
+/// Because this type is primarily intended to help _unsafe_ Rust we should
+/// discuss the precise guarantees offered:
+/// * **Validity Invariants**
+///   * The data layout is a `StableVec` of segments and then a `usize`.
+///   * Each segment's layout is a `*mut u8`, `usize`, `usize`.
+/// * **Soundness Invariants**
+///   * Each segment with a capacity of 0 must be a valid `SharedStr<'a>`.
+///   * Each segment with a non-zero capacity must be a valid `StableString`,
+///     which is owned by the rope.
+///   * The final `usize` must be the total length of all segments.
+///
+/// If you drop a `StableRope` without flattening it then the owned segments
+/// leak, the same as with `StableString`.
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StableRope<'a>`
+/// typedef struct {
+///   uint8_t *ptr;
+///   uintptr_t len;
+///   uintptr_t cap;
+/// } RopeSegment;
+/// typedef struct {
+///   RopeSegment *ptr;
+///   uintptr_t len;
+///   uintptr_t cap;
+/// } StableVec_RopeSegment;
+/// typedef struct {
+///   StableVec_RopeSegment segments;
+///   uintptr_t len;
+/// } StableRope;
+/// ```
+#[repr(C)]
+pub struct StableRope<'a> {
+  segments: StableVec<RopeSegment<'a>>,
+  len: usize,
+}
+
+unsafe impl<'a> StableLayout for StableRope<'a> {}
+
+impl<'a> StableRope<'a> {
+  /// Makes a new, empty rope.
+  #[inline]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// The total length of all the segments, in bytes.
+  #[inline(always)]
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// If the rope has a total length of 0.
+  #[inline(always)]
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// The number of segments in the rope.
+  #[inline(always)]
+  pub fn segment_count(&self) -> usize {
+    self.segments.len()
+  }
+
+  /// Iterates over the segments of the rope in order.
+  #[inline]
+  pub fn segments(&self) -> impl Iterator<Item = &str> + '_ {
+    self.segments.iter().map(RopeSegment::as_str)
+  }
+
+  /// Appends a borrowed string to the end of the rope.
+  #[inline]
+  pub fn push_shared(&mut self, s: SharedStr<'a>) {
+    if s.is_empty() {
+      return;
+    }
+    let s: &'a str = s.into();
+    self.len += s.len();
+    self.segments.with_vec(|vec| {
+      vec.push(RopeSegment {
+        ptr: s.as_ptr() as *mut u8,
+        len: s.len(),
+        cap: 0,
+        life: PhantomData,
+      })
+    });
+  }
+
+  /// Appends an owned string to the end of the rope.
+  ///
+  /// The rope takes ownership of the string's allocation.
+  #[inline]
+  pub fn push_owned(&mut self, s: StableString) {
+    if s.is_empty() {
+      // This also covers everything with a capacity of 0, so owned segments
+      // can't be confused with shared segments.
+      drop(String::from(s));
+      return;
+    }
+    let mut s = core::mem::ManuallyDrop::new(String::from(s));
+    self.len += s.len();
+    let segment = RopeSegment {
+      ptr: s.as_mut_ptr(),
+      len: s.len(),
+      cap: s.capacity(),
+      life: PhantomData,
+    };
+    self.segments.with_vec(|vec| vec.push(segment));
+  }
+
+  /// Copies all the segments into a single string, freeing any owned segments.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let mut rope = StableRope::new();
+  /// rope.push_shared(SharedStr::from("hello"));
+  /// rope.push_owned(StableString::from(String::from(", ")));
+  /// rope.push_shared(SharedStr::from("world"));
+  /// assert_eq!(rope.segment_count(), 3);
+  /// let flat: String = rope.flatten().into();
+  /// assert_eq!(flat, "hello, world");
+  /// ```
+  pub fn flatten(self) -> StableString {
+    let mut out = String::with_capacity(self.len);
+    for segment in Vec::from(self.segments) {
+      out.push_str(segment.as_str());
+      if segment.cap != 0 {
+        // Safety: See note at the top of the module.
+        drop(unsafe {
+          String::from_raw_parts(segment.ptr, segment.len, segment.cap)
+        });
+      }
+    }
+    StableString::from(out)
+  }
+}
+
+impl<'a> Default for StableRope<'a> {
+  /// Defaults to an empty rope.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let rope: StableRope<'static> = StableRope::default();
+  /// assert_eq!(rope.len(), 0);
+  /// ```
+  #[inline(always)]
+  fn default() -> Self {
+    Self { segments: StableVec::default(), len: 0 }
+  }
+}
+
+impl<'a> Debug for StableRope<'a> {
+  /// Debug prints as a list of the segments.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_list().entries(self.segments()).finish()
+  }
+}
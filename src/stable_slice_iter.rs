@@ -0,0 +1,201 @@
+use core::{
+  fmt::Debug, iter::FusedIterator, marker::PhantomData, mem::size_of, slice,
+};
+
+use super::{SharedSlice, StableLayout};
+
+// General Safety Note: The soundness of the `StableSliceIter` type is centered
+// around the fact that the fields are all private, and so *safe rust* must
+// construct values of the type from an existing valid slice. However, because
+// the type is `repr(C)` it can of course be constructed with unsafe rust, or
+// even by foreign code. It is the responsibility of _the other code_ to ensure
+// that the actual fields are valid for being turned into a slice.
+
+/// A slice iterator with a stable layout.
+///
+/// This is a begin / end pointer pair, the same as a C++ iterator range, so
+/// the progress of an iteration can be stored in a C struct and picked up again
+/// on a later FFI call.
+///
+/// ## Unsafety
+///
+/// Because this type is primarily intended to help _unsafe_ Rust we should
+/// discuss the precise guarantees offered:
+/// * **Validity Invariants**
+///   * The data layout is a `*const T` and then another `*const T`.
+/// * **Soundness Invariants**
+///   * The first pointer must point to the start of a valid `&[T]`, and the
+///     second pointer must point one past the end of that same slice.
+///   * If `T` is zero sized then the slice length is instead the difference
+///     between the two pointers' addresses.
+///   * For as long as the `StableSliceIter` exists the memory in question has
+///     a shared borrow over it (tracked via `PhantomData`).
+///
+/// If your element type were `u8` then it would be equivalent layout to the
+/// following C declaration:
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StableSliceIter<'a, u8>`
+/// typedef struct {
+///   uint8_t const *ptr;
+///   uint8_t const *end;
+/// } StableSliceIter_u8;
+/// ```
+#[repr(C)]
+pub struct StableSliceIter<'a, T>
+where
+  T: StableLayout,
+{
+  ptr: *const T,
+  end: *const T,
+  life: PhantomData<&'a [T]>,
+}
+
+unsafe impl<'a, T: StableLayout> StableLayout for StableSliceIter<'a, T> {}
+
+impl<'a, T> StableSliceIter<'a, T>
+where
+  T: StableLayout,
+{
+  /// The elements that haven't been iterated yet.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let data = [1, 2, 3];
+  /// let mut it = StableSliceIter::from(&data[..]);
+  /// assert_eq!(it.next(), Some(&1));
+  /// assert_eq!(it.as_slice(), &[2, 3]);
+  /// assert_eq!(it.next_back(), Some(&3));
+  /// assert_eq!(it.as_slice(), &[2]);
+  /// ```
+  #[inline(always)]
+  pub fn as_slice(&self) -> &'a [T] {
+    // Safety: See note at the top of the module.
+    unsafe { slice::from_raw_parts(self.ptr, self.len()) }
+  }
+}
+
+impl<'a, T: Debug> Debug for StableSliceIter<'a, T>
+where
+  T: StableLayout,
+{
+  /// Debug prints the remaining elements.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_tuple("StableSliceIter").field(&self.as_slice()).finish()
+  }
+}
+
+impl<'a, T> Clone for StableSliceIter<'a, T>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn clone(&self) -> Self {
+    Self { ptr: self.ptr, end: self.end, life: PhantomData }
+  }
+}
+
+impl<'a, T> Default for StableSliceIter<'a, T>
+where
+  T: StableLayout,
+{
+  /// Defaults to an empty iterator.
+  #[inline(always)]
+  fn default() -> Self {
+    Self::from(&[][..])
+  }
+}
+
+impl<'a, T> From<&'a [T]> for StableSliceIter<'a, T>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn from(sli: &'a [T]) -> Self {
+    let ptr = sli.as_ptr();
+    let end = if size_of::<T>() == 0 {
+      ptr.wrapping_byte_add(sli.len())
+    } else {
+      ptr.wrapping_add(sli.len())
+    };
+    Self { ptr, end, life: PhantomData }
+  }
+}
+
+impl<'a, T> From<SharedSlice<'a, T>> for StableSliceIter<'a, T>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn from(shared: SharedSlice<'a, T>) -> Self {
+    Self::from(<&'a [T]>::from(shared))
+  }
+}
+
+impl<'a, T> Iterator for StableSliceIter<'a, T>
+where
+  T: StableLayout,
+{
+  type Item = &'a T;
+  #[inline]
+  fn next(&mut self) -> Option<&'a T> {
+    if self.ptr == self.end {
+      None
+    } else if size_of::<T>() == 0 {
+      self.end = self.end.wrapping_byte_sub(1);
+      // Safety: Any well aligned non-null pointer is valid for a ZST.
+      Some(unsafe { &*self.ptr })
+    } else {
+      let out = self.ptr;
+      // Safety: See note at the top of the module, we're still in bounds.
+      unsafe {
+        self.ptr = self.ptr.add(1);
+        Some(&*out)
+      }
+    }
+  }
+
+  #[inline(always)]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.len();
+    (len, Some(len))
+  }
+}
+
+impl<'a, T> DoubleEndedIterator for StableSliceIter<'a, T>
+where
+  T: StableLayout,
+{
+  #[inline]
+  fn next_back(&mut self) -> Option<&'a T> {
+    if self.ptr == self.end {
+      None
+    } else if size_of::<T>() == 0 {
+      self.end = self.end.wrapping_byte_sub(1);
+      // Safety: Any well aligned non-null pointer is valid for a ZST.
+      Some(unsafe { &*self.ptr })
+    } else {
+      // Safety: See note at the top of the module, we're still in bounds.
+      unsafe {
+        self.end = self.end.sub(1);
+        Some(&*self.end)
+      }
+    }
+  }
+}
+
+impl<'a, T> ExactSizeIterator for StableSliceIter<'a, T>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn len(&self) -> usize {
+    let bytes = (self.end as usize).wrapping_sub(self.ptr as usize);
+    match size_of::<T>() {
+      0 => bytes,
+      size => bytes / size,
+    }
+  }
+}
+
+impl<'a, T> FusedIterator for StableSliceIter<'a, T> where T: StableLayout {}
@@ -0,0 +1,232 @@
+use core::fmt::{Debug, Display};
+
+use super::StableLayout;
+
+#[cfg(feature = "std")]
+use std::io::{Error, ErrorKind};
+
+/// An `errno` style status code with a stable layout.
+///
+/// This is a `repr(transparent)` wrapper over an `i32`, so it's ABI compatible
+/// with a plain `int32_t` return value in C. Because it's a wrapper over an
+/// integer rather than an enum, any value coming from the other side of the
+/// FFI boundary is valid, even ones that this version of the crate doesn't
+/// know about.
+///
+/// The code space is:
+/// * `0` is success ([`StableStatus::OK`]).
+/// * Negative values are the generic error codes defined as associated
+///   constants on this type. They are modeled after
+///   [`std::io::ErrorKind`](https://doc.rust-lang.org/std/io/enum.ErrorKind.html),
+///   and the negative range is reserved for this crate to add more codes to
+///   in the future.
+/// * Positive values are never used by this crate, so your program can assign
+///   them whatever meaning it likes.
+///
+/// ```rust
+/// # use chromium::*;
+/// assert!(StableStatus::OK.is_ok());
+/// assert!(StableStatus::NOT_FOUND.is_err());
+/// assert_eq!(StableStatus::TIMED_OUT.to_result(), Err(StableStatus::TIMED_OUT));
+/// assert_eq!(format!("{}", StableStatus::NOT_FOUND), "NOT_FOUND");
+/// assert_eq!(format!("{}", StableStatus(12)), "StableStatus(12)");
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct StableStatus(pub i32);
+
+unsafe impl StableLayout for StableStatus {}
+
+macro_rules! status_codes {
+  ( $( $(#[$meta:meta])* $name:ident = $value:expr ),* $(,)? ) => {
+    impl StableStatus {
+      $(
+        $(#[$meta])*
+        pub const $name: Self = Self($value);
+      )*
+
+      /// The name of the code, if it's one of the codes defined by this crate.
+      #[inline]
+      pub fn name(self) -> Option<&'static str> {
+        match self.0 {
+          $( $value => Some(stringify!($name)), )*
+          _ => None,
+        }
+      }
+    }
+  };
+}
+
+status_codes! {
+  /// Success.
+  OK = 0,
+  /// An error that doesn't fit any of the other codes.
+  OTHER = -1,
+  /// An entity was not found.
+  NOT_FOUND = -2,
+  /// The operation lacked the necessary privileges.
+  PERMISSION_DENIED = -3,
+  /// The connection was refused by the remote server.
+  CONNECTION_REFUSED = -4,
+  /// The connection was reset by the remote server.
+  CONNECTION_RESET = -5,
+  /// The connection was aborted by the remote server.
+  CONNECTION_ABORTED = -6,
+  /// The operation failed because there's no connection yet.
+  NOT_CONNECTED = -7,
+  /// A socket address could not be bound because it's in use.
+  ADDR_IN_USE = -8,
+  /// A nonexistent interface was requested, or the address wasn't local.
+  ADDR_NOT_AVAILABLE = -9,
+  /// The operation failed because a pipe was closed.
+  BROKEN_PIPE = -10,
+  /// An entity already exists.
+  ALREADY_EXISTS = -11,
+  /// The operation needs to block to complete, but blocking was not allowed.
+  WOULD_BLOCK = -12,
+  /// A parameter was incorrect.
+  INVALID_INPUT = -13,
+  /// Data not valid for the operation was encountered.
+  INVALID_DATA = -14,
+  /// The operation's timeout expired.
+  TIMED_OUT = -15,
+  /// A write returned that zero bytes were written.
+  WRITE_ZERO = -16,
+  /// The operation was interrupted, and can typically be retried.
+  INTERRUPTED = -17,
+  /// The operation is unsupported on this platform (or by this plugin).
+  UNSUPPORTED = -18,
+  /// The end of the input was reached prematurely.
+  UNEXPECTED_EOF = -19,
+  /// Memory could not be allocated.
+  OUT_OF_MEMORY = -20,
+}
+
+impl StableStatus {
+  /// If this is the success code.
+  #[inline(always)]
+  pub const fn is_ok(self) -> bool {
+    self.0 == 0
+  }
+
+  /// If this is anything other than the success code.
+  #[inline(always)]
+  pub const fn is_err(self) -> bool {
+    self.0 != 0
+  }
+
+  /// Turns the status into a `Result`, so that you can use `?` with it.
+  #[inline]
+  pub fn to_result(self) -> Result<(), Self> {
+    if self.is_ok() {
+      Ok(())
+    } else {
+      Err(self)
+    }
+  }
+}
+
+impl From<Result<(), StableStatus>> for StableStatus {
+  /// `Ok` becomes [`StableStatus::OK`], and `Err` becomes the error code.
+  #[inline]
+  fn from(result: Result<(), StableStatus>) -> Self {
+    match result {
+      Ok(()) => Self::OK,
+      Err(status) => status,
+    }
+  }
+}
+
+impl Debug for StableStatus {
+  /// Debug prints the same as Display.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    Display::fmt(self, f)
+  }
+}
+
+impl Display for StableStatus {
+  /// Prints the name of the code, or the number if the code is unknown.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    match self.name() {
+      Some(name) => f.write_str(name),
+      None => write!(f, "StableStatus({})", self.0),
+    }
+  }
+}
+
+#[cfg(feature = "std")]
+impl From<ErrorKind> for StableStatus {
+  /// Kinds without a matching code become [`StableStatus::OTHER`].
+  fn from(kind: ErrorKind) -> Self {
+    match kind {
+      ErrorKind::NotFound => Self::NOT_FOUND,
+      ErrorKind::PermissionDenied => Self::PERMISSION_DENIED,
+      ErrorKind::ConnectionRefused => Self::CONNECTION_REFUSED,
+      ErrorKind::ConnectionReset => Self::CONNECTION_RESET,
+      ErrorKind::ConnectionAborted => Self::CONNECTION_ABORTED,
+      ErrorKind::NotConnected => Self::NOT_CONNECTED,
+      ErrorKind::AddrInUse => Self::ADDR_IN_USE,
+      ErrorKind::AddrNotAvailable => Self::ADDR_NOT_AVAILABLE,
+      ErrorKind::BrokenPipe => Self::BROKEN_PIPE,
+      ErrorKind::AlreadyExists => Self::ALREADY_EXISTS,
+      ErrorKind::WouldBlock => Self::WOULD_BLOCK,
+      ErrorKind::InvalidInput => Self::INVALID_INPUT,
+      ErrorKind::InvalidData => Self::INVALID_DATA,
+      ErrorKind::TimedOut => Self::TIMED_OUT,
+      ErrorKind::WriteZero => Self::WRITE_ZERO,
+      ErrorKind::Interrupted => Self::INTERRUPTED,
+      ErrorKind::Unsupported => Self::UNSUPPORTED,
+      ErrorKind::UnexpectedEof => Self::UNEXPECTED_EOF,
+      ErrorKind::OutOfMemory => Self::OUT_OF_MEMORY,
+      _ => Self::OTHER,
+    }
+  }
+}
+
+#[cfg(feature = "std")]
+impl From<&Error> for StableStatus {
+  /// Converts using the error's kind.
+  #[inline]
+  fn from(error: &Error) -> Self {
+    Self::from(error.kind())
+  }
+}
+
+#[cfg(feature = "std")]
+impl From<Error> for StableStatus {
+  /// Converts using the error's kind.
+  #[inline]
+  fn from(error: Error) -> Self {
+    Self::from(error.kind())
+  }
+}
+
+#[cfg(feature = "std")]
+impl From<StableStatus> for ErrorKind {
+  /// Codes without a matching kind (including [`StableStatus::OK`] and all
+  /// positive codes) become `ErrorKind::Other`.
+  fn from(status: StableStatus) -> Self {
+    match status {
+      StableStatus::NOT_FOUND => ErrorKind::NotFound,
+      StableStatus::PERMISSION_DENIED => ErrorKind::PermissionDenied,
+      StableStatus::CONNECTION_REFUSED => ErrorKind::ConnectionRefused,
+      StableStatus::CONNECTION_RESET => ErrorKind::ConnectionReset,
+      StableStatus::CONNECTION_ABORTED => ErrorKind::ConnectionAborted,
+      StableStatus::NOT_CONNECTED => ErrorKind::NotConnected,
+      StableStatus::ADDR_IN_USE => ErrorKind::AddrInUse,
+      StableStatus::ADDR_NOT_AVAILABLE => ErrorKind::AddrNotAvailable,
+      StableStatus::BROKEN_PIPE => ErrorKind::BrokenPipe,
+      StableStatus::ALREADY_EXISTS => ErrorKind::AlreadyExists,
+      StableStatus::WOULD_BLOCK => ErrorKind::WouldBlock,
+      StableStatus::INVALID_INPUT => ErrorKind::InvalidInput,
+      StableStatus::INVALID_DATA => ErrorKind::InvalidData,
+      StableStatus::TIMED_OUT => ErrorKind::TimedOut,
+      StableStatus::WRITE_ZERO => ErrorKind::WriteZero,
+      StableStatus::INTERRUPTED => ErrorKind::Interrupted,
+      StableStatus::UNSUPPORTED => ErrorKind::Unsupported,
+      StableStatus::UNEXPECTED_EOF => ErrorKind::UnexpectedEof,
+      StableStatus::OUT_OF_MEMORY => ErrorKind::OutOfMemory,
+      _ => ErrorKind::Other,
+    }
+  }
+}
@@ -1,9 +1,14 @@
 #![cfg(feature = "unsafe_alloc")]
 
-use super::StableLayout;
-use alloc::string::String;
+use super::{StableLayout, StableVec};
+use alloc::{
+  collections::TryReserveError,
+  string::{FromUtf16Error, String},
+};
 use core::{
-  fmt::Debug,
+  borrow::{Borrow, BorrowMut},
+  fmt::{Debug, Display},
+  iter::{FromIterator, FusedIterator},
   ops::{Deref, DerefMut},
   slice, str,
 };
@@ -57,6 +62,183 @@ pub struct StableString {
 
 unsafe impl StableLayout for StableString {}
 
+impl StableString {
+  /// Temporarily turns `self` back into a `String` for the duration of `op`.
+  ///
+  /// `self` is left empty while `op` runs, so if `op` panics the memory is
+  /// freed by the `String` and `self` can't be left pointing at it. The buffer
+  /// doesn't really leave `self`, so this isn't counted as a conversion.
+  #[inline]
+  fn with_string<R>(&mut self, op: impl FnOnce(&mut String) -> R) -> R {
+    let mut string = self.take_string_uncounted();
+    let out = op(&mut string);
+    *self = Self::from_string_uncounted(string);
+    out
+  }
+
+  /// Makes a `StableString` from a `String` without counting a conversion.
+  #[inline]
+  fn from_string_uncounted(s: String) -> Self {
+    let mut md_s = core::mem::ManuallyDrop::new(s);
+    let ptr = md_s.as_mut_ptr();
+    Self { ptr, len: md_s.len(), cap: md_s.capacity() }
+  }
+
+  /// Takes the `String` out of `self`, leaving `self` empty, without counting
+  /// a conversion.
+  #[inline]
+  #[track_caller]
+  fn take_string_uncounted(&mut self) -> String {
+    #[cfg(feature = "poison_on_release")]
+    if crate::poison::check(self.ptr, "StableString") {
+      return String::new();
+    }
+    let empty = Self::from_string_uncounted(String::new());
+    let Self { ptr, len, cap } = core::mem::replace(self, empty);
+    // Safety: See note at the top of the module.
+    unsafe { String::from_raw_parts(ptr, len, cap) }
+  }
+
+  /// Makes a string from some bytes, with each invalid UTF-8 sequence replaced
+  /// by U+FFFD (`�`).
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let ss = StableString::from_bytes_lossy(b"hi\xF0\x90there");
+  /// assert_eq!(&ss[..], "hi\u{FFFD}there");
+  /// ```
+  #[inline]
+  pub fn from_bytes_lossy(bytes: &[u8]) -> Self {
+    Self::from(String::from_utf8_lossy(bytes).into_owned())
+  }
+
+  /// Decodes some UTF-16 data, failing if it contains unpaired surrogates.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let wide: Vec<u16> = "hello".encode_utf16().collect();
+  /// assert_eq!(&StableString::from_utf16(&wide).unwrap()[..], "hello");
+  /// assert!(StableString::from_utf16(&[0xD800]).is_err());
+  /// ```
+  #[inline]
+  pub fn from_utf16(wide: &[u16]) -> Result<Self, FromUtf16Error> {
+    String::from_utf16(wide).map(Self::from)
+  }
+
+  /// Decodes some UTF-16 data, with each unpaired surrogate replaced by
+  /// U+FFFD (`�`).
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let ss = StableString::from_utf16_lossy(&[0x68, 0xD800, 0x69]);
+  /// assert_eq!(&ss[..], "h\u{FFFD}i");
+  /// ```
+  #[inline]
+  pub fn from_utf16_lossy(wide: &[u16]) -> Self {
+    Self::from(String::from_utf16_lossy(wide))
+  }
+
+  /// Encodes this string as UTF-16, appending the code units to `out`.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let ss = StableString::from(String::from("h\u{1F600}"));
+  /// let mut wide = StableVec::from(Vec::new());
+  /// ss.encode_utf16_into(&mut wide);
+  /// assert_eq!(&wide[..], &[0x68, 0xD83D, 0xDE00]);
+  /// ```
+  #[inline]
+  pub fn encode_utf16_into(&self, out: &mut StableVec<u16>) {
+    out.with_vec(|v| v.extend(self.encode_utf16()))
+  }
+
+  /// Tries to make an empty string with space for exactly `capacity` bytes,
+  /// giving an error instead of panicking or aborting if that fails.
+  #[inline]
+  pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+    let mut s = String::new();
+    s.try_reserve_exact(capacity)?;
+    Ok(Self::from(s))
+  }
+
+  /// Tries to copy a `str` into a new string.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let ss = StableString::try_from_str("hello").unwrap();
+  /// assert_eq!(&ss[..], "hello");
+  /// ```
+  #[inline]
+  pub fn try_from_str(s: &str) -> Result<Self, TryReserveError> {
+    let mut string = String::new();
+    string.try_reserve_exact(s.len())?;
+    string.push_str(s);
+    Ok(Self::from(string))
+  }
+
+  /// Tries to reserve capacity for at least `additional` more bytes.
+  #[inline]
+  pub fn try_reserve(
+    &mut self, additional: usize,
+  ) -> Result<(), TryReserveError> {
+    self.with_string(|s| s.try_reserve(additional))
+  }
+
+  /// Takes the `String` out of `self`, leaving `self` released.
+  ///
+  /// Normally a released `StableString` is simply empty. With the
+  /// `poison_on_release` feature its fields are instead filled with a poison
+  /// pattern, so that any further use of them (such as from a copy of the
+  /// struct that C code held on to) fails loudly.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let mut ss = StableString::from(String::from("hello"));
+  /// let s = ss.take_string();
+  /// assert_eq!(s, "hello");
+  /// ```
+  #[inline]
+  #[track_caller]
+  pub fn take_string(&mut self) -> String {
+    #[cfg(feature = "poison_on_release")]
+    {
+      if crate::poison::check(self.ptr, "StableString") {
+        return String::new();
+      }
+      let poison = crate::poison::POISON;
+      String::from(core::mem::replace(
+        self,
+        Self { ptr: poison as *mut u8, len: poison, cap: poison },
+      ))
+    }
+    #[cfg(not(feature = "poison_on_release"))]
+    {
+      let empty = Self::from_string_uncounted(String::new());
+      String::from(core::mem::replace(self, empty))
+    }
+  }
+
+  /// Turns the string into an iterator over its `char`s.
+  ///
+  /// The iterator owns the string's allocation, so this reclaims the memory
+  /// just like converting back into a `String` does.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let ss = StableString::from(String::from("héllo"));
+  /// let mut chars = ss.into_chars();
+  /// assert_eq!(chars.next(), Some('h'));
+  /// assert_eq!(chars.next_back(), Some('o'));
+  /// assert_eq!(chars.as_str(), "éll");
+  /// assert_eq!(chars.rev().collect::<String>(), "llé");
+  /// ```
+  #[inline]
+  #[track_caller]
+  pub fn into_chars(self) -> StableStringIntoChars {
+    StableStringIntoChars { string: String::from(self), front: 0 }
+  }
+}
+
 impl Deref for StableString {
   type Target = str;
   #[inline(always)]
@@ -80,6 +262,41 @@ impl DerefMut for StableString {
   }
 }
 
+impl AsRef<str> for StableString {
+  #[inline(always)]
+  fn as_ref(&self) -> &str {
+    self
+  }
+}
+
+impl AsRef<[u8]> for StableString {
+  #[inline(always)]
+  fn as_ref(&self) -> &[u8] {
+    self.as_bytes()
+  }
+}
+
+impl AsMut<str> for StableString {
+  #[inline(always)]
+  fn as_mut(&mut self) -> &mut str {
+    self
+  }
+}
+
+impl Borrow<str> for StableString {
+  #[inline(always)]
+  fn borrow(&self) -> &str {
+    self
+  }
+}
+
+impl BorrowMut<str> for StableString {
+  #[inline(always)]
+  fn borrow_mut(&mut self) -> &mut str {
+    self
+  }
+}
+
 impl Debug for StableString {
   /// Debug prints as a slice would.
   fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
@@ -87,18 +304,80 @@ impl Debug for StableString {
   }
 }
 
+impl Display for StableString {
+  /// Display prints as a `str` would.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    Display::fmt(self.deref(), f)
+  }
+}
+
+/// An owning iterator over the `char`s of a [`StableString`].
+///
+/// This is made with [`StableString::into_chars`]. It's an ordinary Rust
+/// type, not a stable one.
+#[derive(Debug, Clone)]
+pub struct StableStringIntoChars {
+  string: String,
+  /// Byte index of the next `char` from the front.
+  front: usize,
+}
+
+impl StableStringIntoChars {
+  /// The part of the string that hasn't been iterated over yet.
+  #[inline]
+  pub fn as_str(&self) -> &str {
+    &self.string[self.front..]
+  }
+}
+
+impl Iterator for StableStringIntoChars {
+  type Item = char;
+  #[inline]
+  fn next(&mut self) -> Option<char> {
+    let c = self.as_str().chars().next()?;
+    self.front += c.len_utf8();
+    Some(c)
+  }
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.as_str().chars().size_hint()
+  }
+}
+
+impl DoubleEndedIterator for StableStringIntoChars {
+  #[inline]
+  fn next_back(&mut self) -> Option<char> {
+    if self.string.len() > self.front {
+      self.string.pop()
+    } else {
+      None
+    }
+  }
+}
+
+impl FusedIterator for StableStringIntoChars {}
+
 impl From<String> for StableString {
   fn from(s: String) -> Self {
     let mut md_s = core::mem::ManuallyDrop::new(s);
     let cap = md_s.capacity();
     let len = md_s.len();
     let ptr = md_s.as_mut_ptr();
+    trace_conversion!(String => Self, ptr, len);
+    count_conversion!(StableString, Into, len);
     Self { ptr, len, cap }
   }
 }
 
 impl From<StableString> for String {
+  #[track_caller]
   fn from(sv: StableString) -> Self {
+    #[cfg(feature = "poison_on_release")]
+    if crate::poison::check(sv.ptr, "StableString") {
+      return String::new();
+    }
+    trace_conversion!(StableString => Self, sv.ptr, sv.len);
+    count_conversion!(StableString, OutOf, sv.len);
     // Safety: See note at the top of the module.
     unsafe { String::from_raw_parts(sv.ptr, sv.len, sv.cap) }
   }
@@ -117,3 +396,125 @@ impl Default for StableString {
     Self::from(String::default())
   }
 }
+
+impl FromIterator<char> for StableString {
+  /// Collects into a `String` and then converts that.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let ss: StableString = "abc".chars().rev().collect();
+  /// assert_eq!(ss, "cba");
+  /// # let _ = String::from(ss);
+  /// ```
+  #[inline]
+  fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+    Self::from(iter.into_iter().collect::<String>())
+  }
+}
+
+impl<'a> FromIterator<&'a char> for StableString {
+  #[inline]
+  fn from_iter<I: IntoIterator<Item = &'a char>>(iter: I) -> Self {
+    Self::from(iter.into_iter().collect::<String>())
+  }
+}
+
+impl<'a> FromIterator<&'a str> for StableString {
+  #[inline]
+  fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+    Self::from(iter.into_iter().collect::<String>())
+  }
+}
+
+impl FromIterator<String> for StableString {
+  #[inline]
+  fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+    Self::from(iter.into_iter().collect::<String>())
+  }
+}
+
+impl Extend<char> for StableString {
+  /// ```rust
+  /// # use chromium::*;
+  /// let mut ss = StableString::from(String::from("ab"));
+  /// ss.extend(['c', 'd'].iter());
+  /// ss.extend(vec!["ef", "g"]);
+  /// assert_eq!(ss, "abcdefg");
+  /// # let _ = String::from(ss);
+  /// ```
+  #[inline]
+  fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+    self.with_string(|string| string.extend(iter))
+  }
+}
+
+impl<'a> Extend<&'a char> for StableString {
+  #[inline]
+  fn extend<I: IntoIterator<Item = &'a char>>(&mut self, iter: I) {
+    self.with_string(|string| string.extend(iter))
+  }
+}
+
+impl<'a> Extend<&'a str> for StableString {
+  #[inline]
+  fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+    self.with_string(|string| string.extend(iter))
+  }
+}
+
+impl Extend<String> for StableString {
+  #[inline]
+  fn extend<I: IntoIterator<Item = String>>(&mut self, iter: I) {
+    self.with_string(|string| string.extend(iter))
+  }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for StableString {
+  /// Zeroes the bytes _and_ the spare capacity, then sets the length to 0.
+  ///
+  /// A `StableString` leaks its memory when dropped, so it can't be
+  /// `ZeroizeOnDrop`. If you want the memory scrubbed on drop, wrap the
+  /// `StableString` in [`Zeroizing`](zeroize::Zeroizing).
+  #[inline]
+  fn zeroize(&mut self) {
+    self.with_string(|s| s.zeroize())
+  }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for StableString {
+  /// Serializes as a string.
+  #[inline]
+  fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(self)
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for StableString {
+  /// Deserializes a string.
+  #[inline]
+  fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+    String::deserialize(d).map(Self::from)
+  }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for StableString {
+  /// Makes a `String` and then converts it.
+  #[inline]
+  fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+    String::arbitrary(u).map(Self::from)
+  }
+  #[inline]
+  fn arbitrary_take_rest(
+    u: arbitrary::Unstructured<'a>,
+  ) -> arbitrary::Result<Self> {
+    String::arbitrary_take_rest(u).map(Self::from)
+  }
+  #[inline]
+  fn size_hint(depth: usize) -> (usize, Option<usize>) {
+    String::size_hint(depth)
+  }
+}
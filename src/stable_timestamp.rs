@@ -0,0 +1,113 @@
+use super::StableLayout;
+
+/// A wall clock timestamp with a stable layout.
+///
+/// This is the time since the Unix epoch (1970-01-01 00:00:00 UTC), as a
+/// signed count of whole seconds and then the nanoseconds into that second.
+/// Times before the epoch have negative `secs`, but `nanos` always counts
+/// forward, so half a second before the epoch is `-1` seconds and
+/// `500_000_000` nanoseconds.
+///
+/// The `nanos` field should always be less than `1_000_000_000`. The
+/// conversions into Rust types check this and give `None` if it's not.
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StableTimestamp`
+/// typedef struct {
+///   int64_t secs;
+///   uint32_t nanos;
+/// } StableTimestamp;
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(C)]
+pub struct StableTimestamp {
+  /// Whole seconds since the Unix epoch.
+  pub secs: i64,
+  /// Nanoseconds into the second, less than `1_000_000_000`.
+  pub nanos: u32,
+}
+
+unsafe impl StableLayout for StableTimestamp {}
+
+impl StableTimestamp {
+  /// The Unix epoch.
+  pub const UNIX_EPOCH: Self = Self { secs: 0, nanos: 0 };
+
+  /// Makes a timestamp, or gives `None` if `nanos` is a full second or more.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// assert!(StableTimestamp::new(-1, 500_000_000).is_some());
+  /// assert!(StableTimestamp::new(0, 1_000_000_000).is_none());
+  /// ```
+  #[inline(always)]
+  pub const fn new(secs: i64, nanos: u32) -> Option<Self> {
+    if nanos < 1_000_000_000 {
+      Some(Self { secs, nanos })
+    } else {
+      None
+    }
+  }
+
+  /// Turns the timestamp into a `chrono` UTC date time, or gives `None` if
+  /// it's invalid or out of `chrono`'s range.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// use chrono::{TimeZone, Utc};
+  /// let dt = Utc.with_ymd_and_hms(2020, 3, 6, 12, 0, 0).unwrap();
+  /// let stable = StableTimestamp::from(dt);
+  /// assert_eq!(stable.secs, 1_583_496_000);
+  /// assert_eq!(stable.checked_to_date_time(), Some(dt));
+  /// ```
+  #[cfg(feature = "chrono")]
+  #[inline]
+  pub fn checked_to_date_time(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+    if self.nanos < 1_000_000_000 {
+      chrono::DateTime::from_timestamp(self.secs, self.nanos)
+    } else {
+      None
+    }
+  }
+
+  /// Turns the timestamp into a `time` date time with a UTC offset, or gives
+  /// `None` if it's invalid or out of `time`'s range.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// use time::{Duration, OffsetDateTime};
+  /// let odt = OffsetDateTime::UNIX_EPOCH - Duration::milliseconds(500);
+  /// let stable = StableTimestamp::from(odt);
+  /// assert_eq!((stable.secs, stable.nanos), (-1, 500_000_000));
+  /// assert_eq!(stable.checked_to_offset_date_time(), Some(odt));
+  /// ```
+  #[cfg(feature = "time")]
+  #[inline]
+  pub fn checked_to_offset_date_time(&self) -> Option<time::OffsetDateTime> {
+    time::OffsetDateTime::from_unix_timestamp(self.secs)
+      .ok()?
+      .replace_nanosecond(self.nanos)
+      .ok()
+  }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for StableTimestamp {
+  /// A leap second (which `chrono` stores as an extra second's worth of
+  /// nanoseconds) is clamped to the end of the previous second.
+  #[inline]
+  fn from(dt: chrono::DateTime<chrono::Utc>) -> Self {
+    let nanos = dt.timestamp_subsec_nanos().min(999_999_999);
+    Self { secs: dt.timestamp(), nanos }
+  }
+}
+
+#[cfg(feature = "time")]
+impl From<time::OffsetDateTime> for StableTimestamp {
+  /// The offset is dropped, since the timestamp is always relative to UTC.
+  #[inline]
+  fn from(odt: time::OffsetDateTime) -> Self {
+    Self { secs: odt.unix_timestamp(), nanos: odt.nanosecond() }
+  }
+}
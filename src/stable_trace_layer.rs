@@ -0,0 +1,387 @@
+#![cfg(feature = "tracing")]
+
+use core::{ffi::c_void, fmt::Debug};
+use std::{
+  format,
+  string::{String, ToString},
+  vec::Vec,
+};
+
+use tracing_core::{
+  field::{Field, Visit},
+  span, Event, Level, Metadata, Subscriber,
+};
+use tracing_subscriber::layer::{Context, Layer};
+
+use super::{SharedSlice, SharedStr, StableLayout};
+
+/// The static description of a span or event, with a stable layout.
+///
+/// The `level` uses the same numbering as [`StableLogRecord`]: 1 is `ERROR`,
+/// 2 is `WARN`, 3 is `INFO`, 4 is `DEBUG`, and 5 is `TRACE`. The `file` is
+/// empty and the `line` is 0 when they aren't known.
+///
+/// [`StableLogRecord`]: crate::StableLogRecord
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StableTraceMetadata<'a>`
+/// typedef struct {
+///   uint32_t level;
+///   SharedStr name;
+///   SharedStr target;
+///   SharedStr file;
+///   uint32_t line;
+/// } StableTraceMetadata;
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct StableTraceMetadata<'a> {
+  /// The level, from 1 (`ERROR`) to 5 (`TRACE`).
+  pub level: u32,
+  /// The name of the span or event.
+  pub name: SharedStr<'a>,
+  /// The target, usually the module path.
+  pub target: SharedStr<'a>,
+  /// The source file, or empty.
+  pub file: SharedStr<'a>,
+  /// The source line, or 0.
+  pub line: u32,
+}
+
+unsafe impl<'a> StableLayout for StableTraceMetadata<'a> {}
+
+impl<'a> From<&'a Metadata<'a>> for StableTraceMetadata<'a> {
+  #[inline]
+  fn from(metadata: &'a Metadata<'a>) -> Self {
+    let level = match *metadata.level() {
+      Level::ERROR => 1,
+      Level::WARN => 2,
+      Level::INFO => 3,
+      Level::DEBUG => 4,
+      Level::TRACE => 5,
+    };
+    Self {
+      level,
+      name: SharedStr::from(metadata.name()),
+      target: SharedStr::from(metadata.target()),
+      file: SharedStr::from(metadata.file().unwrap_or("")),
+      line: metadata.line().unwrap_or(0),
+    }
+  }
+}
+
+/// A field name and its formatted value, with a stable layout.
+///
+/// Strings are passed as they are, and every other value is formatted with
+/// `Debug`. An event's message is the field named `message`.
+///
+/// ```c
+/// // Identical layout to `StableTraceField<'a>`
+/// typedef struct {
+///   SharedStr name;
+///   SharedStr value;
+/// } StableTraceField;
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct StableTraceField<'a> {
+  /// The field's name.
+  pub name: SharedStr<'a>,
+  /// The field's value.
+  pub value: SharedStr<'a>,
+}
+
+unsafe impl<'a> StableLayout for StableTraceField<'a> {}
+
+/// A new span, with a stable layout.
+///
+/// Span ids are never 0, so a `parent` of 0 means that the span is a root.
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StableSpanRecord<'a>`
+/// typedef struct {
+///   uint64_t id;
+///   uint64_t parent;
+///   StableTraceMetadata metadata;
+///   SharedSlice_StableTraceField fields;
+/// } StableSpanRecord;
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct StableSpanRecord<'a> {
+  /// The span's id.
+  pub id: u64,
+  /// The parent span's id, or 0.
+  pub parent: u64,
+  /// The span's metadata.
+  pub metadata: StableTraceMetadata<'a>,
+  /// The span's fields.
+  pub fields: SharedSlice<'a, StableTraceField<'a>>,
+}
+
+unsafe impl<'a> StableLayout for StableSpanRecord<'a> {}
+
+/// An event, with a stable layout.
+///
+/// A `span` of 0 means that the event isn't inside of any span.
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StableEventRecord<'a>`
+/// typedef struct {
+///   uint64_t span;
+///   StableTraceMetadata metadata;
+///   SharedSlice_StableTraceField fields;
+/// } StableEventRecord;
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct StableEventRecord<'a> {
+  /// The id of the span the event is in, or 0.
+  pub span: u64,
+  /// The event's metadata.
+  pub metadata: StableTraceMetadata<'a>,
+  /// The event's fields.
+  pub fields: SharedSlice<'a, StableTraceField<'a>>,
+}
+
+unsafe impl<'a> StableLayout for StableEventRecord<'a> {}
+
+/// The callbacks that a [`StableTraceLayer`] forwards to.
+///
+/// Each callback gets the `context` pointer as its first argument. Any of the
+/// callbacks can be null, in which case that kind of notification is skipped.
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StableTraceCallbacks`
+/// typedef struct {
+///   void *context;
+///   void (*new_span)(void *, StableSpanRecord);
+///   void (*record)(void *, uint64_t, SharedSlice_StableTraceField);
+///   void (*event)(void *, StableEventRecord);
+///   void (*enter)(void *, uint64_t);
+///   void (*exit)(void *, uint64_t);
+///   void (*close)(void *, uint64_t);
+/// } StableTraceCallbacks;
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct StableTraceCallbacks {
+  /// Passed to every callback.
+  pub context: *mut c_void,
+  /// A span was made.
+  pub new_span:
+    Option<for<'a> unsafe extern "C" fn(*mut c_void, StableSpanRecord<'a>)>,
+  /// More fields were recorded on an existing span.
+  pub record: Option<
+    for<'a> unsafe extern "C" fn(
+      *mut c_void,
+      u64,
+      SharedSlice<'a, StableTraceField<'a>>,
+    ),
+  >,
+  /// An event happened.
+  pub event:
+    Option<for<'a> unsafe extern "C" fn(*mut c_void, StableEventRecord<'a>)>,
+  /// A span was entered.
+  pub enter: Option<unsafe extern "C" fn(*mut c_void, u64)>,
+  /// A span was exited.
+  pub exit: Option<unsafe extern "C" fn(*mut c_void, u64)>,
+  /// A span was closed, and its id might be reused.
+  pub close: Option<unsafe extern "C" fn(*mut c_void, u64)>,
+}
+
+unsafe impl StableLayout for StableTraceCallbacks {}
+
+/// A `tracing-subscriber` layer that forwards spans and events across FFI.
+///
+/// A plugin gets a [`StableTraceCallbacks`] from the host, makes a layer with
+/// it, and adds the layer to the plugin's subscriber. Every span and event in
+/// the plugin is then passed to the callbacks as stable records, so the two
+/// sides don't need to share any Rust types, or even the same compiler.
+///
+/// This type itself is _not_ `repr(C)`, it lives on the plugin side.
+///
+/// ```rust
+/// # use chromium::*;
+/// # use core::ffi::c_void;
+/// # use std::sync::Mutex;
+/// use tracing_subscriber::layer::SubscriberExt;
+/// static SEEN: Mutex<Vec<String>> = Mutex::new(Vec::new());
+/// // This would be in the host.
+/// unsafe extern "C" fn on_event(_: *mut c_void, rec: StableEventRecord<'_>) {
+///   let mut line = format!("[{}]", rec.span);
+///   for field in rec.fields.iter() {
+///     line += &format!(" {}={}", &*field.name, &*field.value);
+///   }
+///   SEEN.lock().unwrap().push(line);
+/// }
+/// let callbacks = StableTraceCallbacks {
+///   context: core::ptr::null_mut(),
+///   new_span: None,
+///   record: None,
+///   event: Some(on_event),
+///   enter: None,
+///   exit: None,
+///   close: None,
+/// };
+/// // And this would be in the plugin.
+/// let layer = unsafe { StableTraceLayer::new(callbacks) };
+/// let subscriber = tracing_subscriber::registry().with(layer);
+/// tracing::subscriber::with_default(subscriber, || {
+///   tracing::info!(count = 3, "loaded");
+/// });
+/// assert_eq!(&SEEN.lock().unwrap()[..], &["[0] message=loaded count=3"]);
+/// ```
+pub struct StableTraceLayer {
+  callbacks: StableTraceCallbacks,
+}
+
+// Safety: `new` requires that the callbacks be callable from any thread with
+// the context pointer.
+unsafe impl Send for StableTraceLayer {}
+unsafe impl Sync for StableTraceLayer {}
+
+impl StableTraceLayer {
+  /// Makes a layer that forwards to `callbacks`.
+  ///
+  /// ## Safety
+  /// * Every non-null callback must be safe to call with the `context` pointer
+  ///   from any thread, at any time for as long as the layer exists.
+  #[inline]
+  pub const unsafe fn new(callbacks: StableTraceCallbacks) -> Self {
+    Self { callbacks }
+  }
+}
+
+impl Debug for StableTraceLayer {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_struct("StableTraceLayer")
+      .field("context", &self.callbacks.context)
+      .finish()
+  }
+}
+
+/// Collects field values as strings.
+#[derive(Default)]
+struct FieldStrings(Vec<(&'static str, String)>);
+
+impl Visit for FieldStrings {
+  fn record_str(&mut self, field: &Field, value: &str) {
+    self.0.push((field.name(), value.to_string()));
+  }
+  fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+    self.0.push((field.name(), format!("{:?}", value)));
+  }
+}
+
+impl FieldStrings {
+  /// Runs `op` with stable views of the fields.
+  fn with_stable<R>(
+    &self, op: impl FnOnce(SharedSlice<'_, StableTraceField<'_>>) -> R,
+  ) -> R {
+    let fields: Vec<StableTraceField<'_>> = self
+      .0
+      .iter()
+      .map(|(name, value)| StableTraceField {
+        name: SharedStr::from(*name),
+        value: SharedStr::from(value.as_str()),
+      })
+      .collect();
+    op(SharedSlice::from(&fields[..]))
+  }
+}
+
+/// The id as a `u64`, or 0 for `None`.
+#[inline]
+fn id_u64(id: Option<&span::Id>) -> u64 {
+  id.map(span::Id::into_u64).unwrap_or(0)
+}
+
+impl<S: Subscriber> Layer<S> for StableTraceLayer {
+  fn on_new_span(
+    &self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>,
+  ) {
+    let callback = match self.callbacks.new_span {
+      Some(callback) => callback,
+      None => return,
+    };
+    let parent = if attrs.is_contextual() {
+      id_u64(ctx.current_span().id())
+    } else {
+      id_u64(attrs.parent())
+    };
+    let mut values = FieldStrings::default();
+    attrs.record(&mut values);
+    values.with_stable(|fields| {
+      let record = StableSpanRecord {
+        id: id.into_u64(),
+        parent,
+        metadata: StableTraceMetadata::from(attrs.metadata()),
+        fields,
+      };
+      // Safety: See the requirements of `new`.
+      unsafe { callback(self.callbacks.context, record) }
+    })
+  }
+
+  fn on_record(
+    &self, id: &span::Id, values: &span::Record<'_>, _ctx: Context<'_, S>,
+  ) {
+    if let Some(callback) = self.callbacks.record {
+      let mut strings = FieldStrings::default();
+      values.record(&mut strings);
+      strings.with_stable(|fields| {
+        // Safety: See the requirements of `new`.
+        unsafe { callback(self.callbacks.context, id.into_u64(), fields) }
+      })
+    }
+  }
+
+  fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+    let callback = match self.callbacks.event {
+      Some(callback) => callback,
+      None => return,
+    };
+    let span = if event.is_contextual() {
+      id_u64(ctx.current_span().id())
+    } else {
+      id_u64(event.parent())
+    };
+    let mut values = FieldStrings::default();
+    event.record(&mut values);
+    values.with_stable(|fields| {
+      let record = StableEventRecord {
+        span,
+        metadata: StableTraceMetadata::from(event.metadata()),
+        fields,
+      };
+      // Safety: See the requirements of `new`.
+      unsafe { callback(self.callbacks.context, record) }
+    })
+  }
+
+  fn on_enter(&self, id: &span::Id, _ctx: Context<'_, S>) {
+    if let Some(callback) = self.callbacks.enter {
+      // Safety: See the requirements of `new`.
+      unsafe { callback(self.callbacks.context, id.into_u64()) }
+    }
+  }
+
+  fn on_exit(&self, id: &span::Id, _ctx: Context<'_, S>) {
+    if let Some(callback) = self.callbacks.exit {
+      // Safety: See the requirements of `new`.
+      unsafe { callback(self.callbacks.context, id.into_u64()) }
+    }
+  }
+
+  fn on_close(&self, id: span::Id, _ctx: Context<'_, S>) {
+    if let Some(callback) = self.callbacks.close {
+      // Safety: See the requirements of `new`.
+      unsafe { callback(self.callbacks.context, id.into_u64()) }
+    }
+  }
+}
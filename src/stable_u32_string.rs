@@ -0,0 +1,182 @@
+#![cfg(feature = "unsafe_alloc")]
+
+use super::{LeBytes, SharedU32Str, StableLayout, StableVec};
+use alloc::{string::String, vec::Vec};
+use core::{
+  fmt::{Debug, Display},
+  iter::FromIterator,
+  ops::{Deref, DerefMut},
+};
+
+/// An owned UTF-32 string with a stable layout.
+///
+/// This is a [`StableVec<char>`](StableVec) that's specifically meant to be
+/// text, the owned partner of [`SharedU32Str`]. Like any `StableVec` it leaks
+/// its memory if you drop it without turning it back into a Rust type.
+///
+/// ## Unsafety
+///
+/// This has all the same invariants as `StableVec<char>`, which means that
+/// every element must be a Unicode scalar value.
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StableU32String`
+/// typedef struct {
+///   uint32_t *ptr;
+///   uintptr_t len;
+///   uintptr_t cap;
+/// } StableU32String;
+/// ```
+#[repr(transparent)]
+pub struct StableU32String {
+  chars: StableVec<char>,
+}
+
+unsafe impl StableLayout for StableU32String {}
+
+impl StableU32String {
+  /// Checks that every `u32` is a Unicode scalar value, and then copies them
+  /// into a new string.
+  #[inline]
+  pub fn from_u32s(data: &[u32]) -> Option<Self> {
+    SharedU32Str::from_u32s(data).map(|s| s.chars().collect())
+  }
+
+  /// Borrows the string as a shared view.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let wide = StableU32String::from("héllo");
+  /// assert_eq!(wide.len(), 5);
+  /// assert_eq!(format!("{}", wide.as_shared()), "héllo");
+  /// assert_eq!(String::from(wide), "héllo");
+  /// ```
+  #[inline(always)]
+  pub fn as_shared(&self) -> SharedU32Str<'_> {
+    SharedU32Str::from(&self.chars[..])
+  }
+}
+
+impl StableU32String {
+  /// Writes the string out as little-endian UTF-32.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let wide = StableU32String::from("hi");
+  /// let bytes = wide.to_le_bytes();
+  /// assert_eq!(&bytes[..], &[b'h', 0, 0, 0, b'i', 0, 0, 0]);
+  /// let back = StableU32String::from_le_bytes(&bytes).unwrap();
+  /// assert_eq!(String::from(back), "hi");
+  /// ```
+  #[inline]
+  pub fn to_le_bytes(&self) -> StableVec<u8> {
+    let mut out = Vec::with_capacity(self.len() * 4);
+    for &c in self.iter() {
+      out.extend_from_slice(&u32::from(c).to_le_bytes());
+    }
+    StableVec::from(out)
+  }
+
+  /// Reads a string from little-endian UTF-32.
+  ///
+  /// Gives `None` if the number of bytes isn't a multiple of 4, or if any
+  /// value isn't a Unicode scalar value.
+  #[inline]
+  pub fn from_le_bytes(bytes: &[u8]) -> Option<Self> {
+    let chunks = bytes.chunks_exact(4);
+    if !chunks.remainder().is_empty() {
+      return None;
+    }
+    chunks.map(|c| char::from_u32(u32::read_le(c))).collect()
+  }
+}
+
+impl Deref for StableU32String {
+  type Target = [char];
+  #[inline(always)]
+  fn deref(&self) -> &[char] {
+    &self.chars
+  }
+}
+
+impl DerefMut for StableU32String {
+  #[inline(always)]
+  fn deref_mut(&mut self) -> &mut [char] {
+    &mut self.chars
+  }
+}
+
+impl Debug for StableU32String {
+  /// Debug prints as a `str` would.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    Debug::fmt(&self.as_shared(), f)
+  }
+}
+
+impl Display for StableU32String {
+  /// Display prints as a `str` would.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    Display::fmt(&self.as_shared(), f)
+  }
+}
+
+impl Default for StableU32String {
+  /// Defaults to an empty string.
+  #[inline(always)]
+  fn default() -> Self {
+    Self { chars: StableVec::default() }
+  }
+}
+
+impl FromIterator<char> for StableU32String {
+  #[inline]
+  fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+    Self::from(iter.into_iter().collect::<Vec<char>>())
+  }
+}
+
+impl From<&str> for StableU32String {
+  #[inline]
+  fn from(s: &str) -> Self {
+    s.chars().collect()
+  }
+}
+
+impl From<Vec<char>> for StableU32String {
+  #[inline]
+  fn from(v: Vec<char>) -> Self {
+    Self { chars: StableVec::from(v) }
+  }
+}
+
+impl From<StableVec<char>> for StableU32String {
+  #[inline(always)]
+  fn from(chars: StableVec<char>) -> Self {
+    Self { chars }
+  }
+}
+
+impl From<StableU32String> for StableVec<char> {
+  #[inline(always)]
+  fn from(s: StableU32String) -> Self {
+    s.chars
+  }
+}
+
+impl From<StableU32String> for Vec<char> {
+  #[inline]
+  #[track_caller]
+  fn from(s: StableU32String) -> Self {
+    Vec::from(s.chars)
+  }
+}
+
+impl From<StableU32String> for String {
+  /// Encodes the string as UTF-8, freeing the UTF-32 buffer.
+  #[inline]
+  #[track_caller]
+  fn from(s: StableU32String) -> Self {
+    Vec::from(s).into_iter().collect()
+  }
+}
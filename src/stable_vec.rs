@@ -1,9 +1,16 @@
 #![cfg(feature = "unsafe_alloc")]
 
-use super::StableLayout;
-use alloc::vec::Vec;
+use super::{LeBytes, StableLayout, UniqueSlice};
+use alloc::{
+  alloc::{alloc_zeroed, handle_alloc_error, Layout},
+  collections::TryReserveError,
+  vec::Vec,
+};
 use core::{
+  borrow::{Borrow, BorrowMut},
   fmt::Debug,
+  iter::FromIterator,
+  mem::MaybeUninit,
   ops::{Deref, DerefMut},
   slice,
 };
@@ -33,6 +40,10 @@ use core::{
 ///       different global allocator than the one is was created with. At the
 ///       moment (2020-03-06) it happens to be the case that the default Rust
 ///       global allocator is process-wide on Windows / Mac / Linux.
+///     * With the `nightly_allocator_api` feature, memory from some other
+///       allocator can be carried too, as long as it's only ever turned back
+///       into a `Vec` with [`into_vec_in`](Self::into_vec_in) and that
+///       allocator.
 ///
 /// If you drop a `StableVec` without turning it back into a `Vec` then the
 /// memory leaks.
@@ -67,6 +78,273 @@ where
 
 unsafe impl<T: StableLayout> StableLayout for StableVec<T> {}
 
+impl<T> StableVec<T>
+where
+  T: StableLayout,
+{
+  /// Temporarily turns `self` back into a `Vec` for the duration of `op`.
+  ///
+  /// `self` is left empty while `op` runs, so if `op` panics the memory is
+  /// freed by the `Vec` and `self` can't be left pointing at it. The buffer
+  /// doesn't really leave `self`, so this isn't counted as a conversion.
+  #[inline]
+  pub(crate) fn with_vec<R>(&mut self, op: impl FnOnce(&mut Vec<T>) -> R) -> R {
+    let mut vec = self.take_vec_uncounted();
+    let out = op(&mut vec);
+    *self = Self::from_vec_uncounted(vec);
+    out
+  }
+
+  /// Makes a `StableVec` from a `Vec` without counting a conversion.
+  #[inline]
+  fn from_vec_uncounted(vec: Vec<T>) -> Self {
+    let mut md_vec = core::mem::ManuallyDrop::new(vec);
+    let ptr = md_vec.as_mut_ptr();
+    Self { ptr, len: md_vec.len(), cap: md_vec.capacity() }
+  }
+
+  /// Takes the `Vec` out of `self`, leaving `self` empty, without counting a
+  /// conversion.
+  #[inline]
+  #[track_caller]
+  fn take_vec_uncounted(&mut self) -> Vec<T> {
+    #[cfg(feature = "poison_on_release")]
+    if crate::poison::check(self.ptr, "StableVec") {
+      return Vec::new();
+    }
+    let empty = Self::from_vec_uncounted(Vec::new());
+    let Self { ptr, len, cap } = core::mem::replace(self, empty);
+    // Safety: See note at the top of the module.
+    unsafe { Vec::from_raw_parts(ptr, len, cap) }
+  }
+
+  /// Makes a vec of `len` copies of `value`.
+  ///
+  /// This is the same as `vec![value; len]`, so a zero value of a primitive
+  /// type gets zero-filled memory straight from the allocator.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let sv = StableVec::from_elem(7_u8, 3);
+  /// assert_eq!(&sv[..], &[7, 7, 7]);
+  /// ```
+  ///
+  /// ## Panics
+  /// If the new capacity overflows `isize::MAX` bytes.
+  #[inline]
+  pub fn from_elem(value: T, len: usize) -> Self
+  where
+    T: Clone,
+  {
+    Self::from(alloc::vec![value; len])
+  }
+
+  /// Makes a vec of `len` elements that are all zeroed memory.
+  ///
+  /// The memory comes from the allocator's zero-filling allocation function,
+  /// so large buffers don't have to be written to first.
+  ///
+  /// Gives `None` if `len` elements would overflow `isize::MAX` bytes.
+  ///
+  /// ## Safety
+  /// The all-zero bit pattern must be a valid value of `T`.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let sv: StableVec<u32> = unsafe { StableVec::zeroed(4) }.unwrap();
+  /// assert_eq!(&sv[..], &[0, 0, 0, 0]);
+  /// assert!(unsafe { StableVec::<u32>::zeroed(usize::MAX) }.is_none());
+  /// ```
+  #[inline]
+  pub unsafe fn zeroed(len: usize) -> Option<Self> {
+    let layout = Layout::array::<T>(len).ok()?;
+    let mut vec = if layout.size() == 0 {
+      Vec::with_capacity(len)
+    } else {
+      let ptr = alloc_zeroed(layout).cast::<T>();
+      if ptr.is_null() {
+        handle_alloc_error(layout)
+      }
+      // Safety: the pointer is from the global allocator with the layout of
+      // exactly `len` elements.
+      Vec::from_raw_parts(ptr, 0, len)
+    };
+    // Safety: the caller promises that zeroed memory is a valid `T`.
+    vec.set_len(len);
+    Some(Self::from(vec))
+  }
+
+  /// Takes the `Vec` out of `self`, leaving `self` released.
+  ///
+  /// Normally a released `StableVec` is simply empty. With the
+  /// `poison_on_release` feature its fields are instead filled with a poison
+  /// pattern, so that any further use of them (such as from a copy of the
+  /// struct that C code held on to) fails loudly.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let mut sv = StableVec::from(vec![1, 2, 3]);
+  /// let v = sv.take_vec();
+  /// assert_eq!(v, vec![1, 2, 3]);
+  /// ```
+  #[inline]
+  #[track_caller]
+  pub fn take_vec(&mut self) -> Vec<T> {
+    #[cfg(feature = "poison_on_release")]
+    {
+      if crate::poison::check(self.ptr, "StableVec") {
+        return Vec::new();
+      }
+      let poison = crate::poison::POISON;
+      Vec::from(core::mem::replace(
+        self,
+        Self { ptr: poison as *mut T, len: poison, cap: poison },
+      ))
+    }
+    #[cfg(not(feature = "poison_on_release"))]
+    {
+      let empty = Self::from_vec_uncounted(Vec::new());
+      Vec::from(core::mem::replace(self, empty))
+    }
+  }
+
+  /// The number of elements the vec can hold without reallocating.
+  #[inline(always)]
+  pub fn capacity(&self) -> usize {
+    self.cap
+  }
+
+  /// Reserves capacity for at least `additional` more elements.
+  ///
+  /// ## Panics
+  /// If the new capacity overflows `isize::MAX` bytes.
+  #[inline]
+  pub fn reserve(&mut self, additional: usize) {
+    self.with_vec(|vec| vec.reserve(additional))
+  }
+
+  /// Tries to reserve capacity for at least `additional` more elements,
+  /// giving an error instead of panicking or aborting if that fails.
+  #[inline]
+  pub fn try_reserve(
+    &mut self, additional: usize,
+  ) -> Result<(), TryReserveError> {
+    self.with_vec(|vec| vec.try_reserve(additional))
+  }
+
+  /// Tries to make an empty vec with space for exactly `capacity` elements.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let sv = StableVec::<u64>::try_with_capacity(10).unwrap();
+  /// assert!(sv.capacity() >= 10);
+  /// assert!(StableVec::<u64>::try_with_capacity(usize::MAX).is_err());
+  /// ```
+  #[inline]
+  pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+    let mut vec = Vec::new();
+    vec.try_reserve_exact(capacity)?;
+    Ok(Self::from(vec))
+  }
+
+  /// Tries to clone a slice into a new vec.
+  #[inline]
+  pub fn try_from_slice(sli: &[T]) -> Result<Self, TryReserveError>
+  where
+    T: Clone,
+  {
+    let mut vec = Vec::new();
+    vec.try_reserve_exact(sli.len())?;
+    vec.extend_from_slice(sli);
+    Ok(Self::from(vec))
+  }
+
+  /// The unused capacity past the end of the vec.
+  ///
+  /// Write elements to the front of this (such as by passing it to foreign
+  /// code) and then call [`assume_init_grow`](Self::assume_init_grow) to add
+  /// them to the vec, without an extra copy.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let mut sv = StableVec::from(vec![1_u8]);
+  /// sv.try_reserve(3).unwrap();
+  /// let mut spare = sv.spare_capacity_mut();
+  /// spare[0].write(2);
+  /// spare[1].write(3);
+  /// unsafe { sv.assume_init_grow(2) };
+  /// assert_eq!(&sv[..], &[1, 2, 3]);
+  /// ```
+  #[inline(always)]
+  pub fn spare_capacity_mut(&mut self) -> UniqueSlice<'_, MaybeUninit<T>> {
+    // Safety: See note at the top of the module. The capacity past the length
+    // is part of the allocation.
+    UniqueSlice::from(unsafe {
+      slice::from_raw_parts_mut(
+        self.ptr.add(self.len) as *mut MaybeUninit<T>,
+        self.cap - self.len,
+      )
+    })
+  }
+
+  /// Adds `n` elements from the spare capacity to the end of the vec.
+  ///
+  /// ## Safety
+  /// * `n` must be no more than the spare capacity.
+  /// * The first `n` elements of the spare capacity must be initialized.
+  #[inline(always)]
+  pub unsafe fn assume_init_grow(&mut self, n: usize) {
+    debug_assert!(n <= self.cap - self.len);
+    self.len += n;
+  }
+
+  /// Splits the vec in two at the given index.
+  ///
+  /// Afterwards `self` holds `[0, at)` and the returned vec holds `[at, len)`.
+  ///
+  /// ## Panics
+  /// If `at > len`.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let mut sv = StableVec::from(vec![1, 2, 3, 4]);
+  /// let tail = sv.split_off(1);
+  /// assert_eq!(&sv[..], &[1]);
+  /// assert_eq!(&tail[..], &[2, 3, 4]);
+  /// ```
+  #[inline]
+  pub fn split_off(&mut self, at: usize) -> Self {
+    Self::from(self.with_vec(|vec| vec.split_off(at)))
+  }
+
+  /// Like [`split_off`](Self::split_off), but gives `None` if `at > len`.
+  #[inline]
+  pub fn try_split_off(&mut self, at: usize) -> Option<Self> {
+    // Check the `Vec`, since with `no_panic` a poisoned `self` acts empty.
+    self
+      .with_vec(|vec| (at <= vec.len()).then(|| vec.split_off(at)))
+      .map(Self::from)
+  }
+
+  /// Retains only the elements for which `f` returns `true`.
+  ///
+  /// This works in place and keeps the order of the retained elements.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let mut sv = StableVec::from(vec![1, 2, 3, 4]);
+  /// sv.retain(|x| x % 2 == 0);
+  /// assert_eq!(&sv[..], &[2, 4]);
+  /// ```
+  #[inline]
+  pub fn retain<F>(&mut self, f: F)
+  where
+    F: FnMut(&T) -> bool,
+  {
+    self.with_vec(|vec| vec.retain(f))
+  }
+}
+
 impl<T> Deref for StableVec<T>
 where
   T: StableLayout,
@@ -90,6 +368,91 @@ where
   }
 }
 
+impl<T> AsRef<[T]> for StableVec<T>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn as_ref(&self) -> &[T] {
+    self
+  }
+}
+
+impl<T> AsMut<[T]> for StableVec<T>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn as_mut(&mut self) -> &mut [T] {
+    self
+  }
+}
+
+impl<T> Borrow<[T]> for StableVec<T>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn borrow(&self) -> &[T] {
+    self
+  }
+}
+
+impl<T> BorrowMut<[T]> for StableVec<T>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn borrow_mut(&mut self) -> &mut [T] {
+    self
+  }
+}
+
+impl<'b, T> IntoIterator for &'b StableVec<T>
+where
+  T: StableLayout,
+{
+  type Item = &'b T;
+  type IntoIter = slice::Iter<'b, T>;
+  #[inline(always)]
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'b, T> IntoIterator for &'b mut StableVec<T>
+where
+  T: StableLayout,
+{
+  type Item = &'b mut T;
+  type IntoIter = slice::IterMut<'b, T>;
+  #[inline(always)]
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter_mut()
+  }
+}
+
+impl<T> IntoIterator for StableVec<T>
+where
+  T: StableLayout,
+{
+  type Item = T;
+  type IntoIter = alloc::vec::IntoIter<T>;
+  /// Turns the `StableVec` back into a `Vec` and iterates over that.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let sv = StableVec::from(vec![1, 2, 3]);
+  /// let doubled: Vec<i32> = sv.into_iter().map(|x| x * 2).collect();
+  /// assert_eq!(doubled, [2, 4, 6]);
+  /// ```
+  #[inline]
+  #[track_caller]
+  fn into_iter(self) -> Self::IntoIter {
+    Vec::from(self).into_iter()
+  }
+}
+
 impl<T: Debug> Debug for StableVec<T>
 where
   T: StableLayout,
@@ -109,6 +472,8 @@ where
     let cap = md_vec.capacity();
     let len = md_vec.len();
     let ptr = md_vec.as_mut_ptr();
+    trace_conversion!(Vec<T> => Self, ptr, len);
+    count_conversion!(StableVec, Into, len * core::mem::size_of::<T>());
     Self { ptr, len, cap }
   }
 }
@@ -117,12 +482,112 @@ impl<T> From<StableVec<T>> for Vec<T>
 where
   T: StableLayout,
 {
+  #[track_caller]
   fn from(sv: StableVec<T>) -> Self {
+    #[cfg(feature = "poison_on_release")]
+    if crate::poison::check(sv.ptr, "StableVec") {
+      return Vec::new();
+    }
+    trace_conversion!(StableVec<T> => Self, sv.ptr, sv.len);
+    count_conversion!(StableVec, OutOf, sv.len * core::mem::size_of::<T>());
     // Safety: See note at the top of the module.
     unsafe { Vec::from_raw_parts(sv.ptr, sv.len, sv.cap) }
   }
 }
 
+impl<T> StableVec<T>
+where
+  T: StableLayout + LeBytes,
+{
+  /// Writes the elements out as little-endian bytes.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let sv = StableVec::from(vec![1_u16, 0x0302]);
+  /// let bytes = sv.to_le_bytes();
+  /// assert_eq!(&bytes[..], &[1, 0, 2, 3]);
+  /// let back = StableVec::<u16>::from_le_bytes(&bytes).unwrap();
+  /// assert_eq!(&back[..], &[1, 0x0302]);
+  /// ```
+  #[inline]
+  pub fn to_le_bytes(&self) -> StableVec<u8> {
+    let mut out = alloc::vec![0_u8; self.len * T::SIZE];
+    for (x, chunk) in self.iter().zip(out.chunks_exact_mut(T::SIZE)) {
+      x.write_le(chunk);
+    }
+    StableVec::from(out)
+  }
+
+  /// Reads elements from little-endian bytes.
+  ///
+  /// Gives `None` if the number of bytes isn't a multiple of the element size.
+  #[inline]
+  pub fn from_le_bytes(bytes: &[u8]) -> Option<Self> {
+    let chunks = bytes.chunks_exact(T::SIZE);
+    if !chunks.remainder().is_empty() {
+      return None;
+    }
+    Some(Self::from(chunks.map(T::read_le).collect::<Vec<T>>()))
+  }
+}
+
+#[cfg(feature = "nightly_allocator_api")]
+impl<T> StableVec<T>
+where
+  T: StableLayout,
+{
+  /// Takes apart a `Vec` with a custom allocator, giving back the allocator
+  /// separately.
+  ///
+  /// The allocator must be kept around (or be able to be recreated) so that
+  /// the memory can be given back to it with
+  /// [`into_vec_in`](Self::into_vec_in). If you want to keep the allocator
+  /// in the struct itself, use [`StableVecIn`](crate::StableVecIn) instead.
+  ///
+  /// ```rust
+  /// #![feature(allocator_api)]
+  /// # use chromium::*;
+  /// use std::alloc::System;
+  /// let mut v = Vec::new_in(System);
+  /// v.extend_from_slice(&[1, 2, 3]);
+  /// let (sv, alloc) = StableVec::from_vec_in(v);
+  /// assert_eq!(&sv[..], &[1, 2, 3]);
+  /// let v = unsafe { sv.into_vec_in(alloc) };
+  /// assert_eq!(v, [1, 2, 3]);
+  /// ```
+  #[inline]
+  pub fn from_vec_in<A: core::alloc::Allocator>(vec: Vec<T, A>) -> (Self, A) {
+    let mut md_vec = core::mem::ManuallyDrop::new(vec);
+    // Safety: The `Vec` is never dropped, so the allocator isn't duplicated.
+    let alloc = unsafe { core::ptr::read(md_vec.allocator()) };
+    let cap = md_vec.capacity();
+    let len = md_vec.len();
+    let ptr = md_vec.as_mut_ptr();
+    trace_conversion!(Vec<T, A> => Self, ptr, len);
+    count_conversion!(StableVec, Into, len * core::mem::size_of::<T>());
+    (Self { ptr, len, cap }, alloc)
+  }
+
+  /// Turns this back into a `Vec` that uses the allocator given.
+  ///
+  /// ## Safety
+  /// * The memory must have been allocated by `alloc` (or by an allocator that
+  ///   `alloc` is able to free memory from).
+  #[inline]
+  #[track_caller]
+  pub unsafe fn into_vec_in<A: core::alloc::Allocator>(
+    self, alloc: A,
+  ) -> Vec<T, A> {
+    #[cfg(feature = "poison_on_release")]
+    if crate::poison::check(self.ptr, "StableVec") {
+      return Vec::new_in(alloc);
+    }
+    trace_conversion!(Self => Vec<T, A>, self.ptr, self.len);
+    count_conversion!(StableVec, OutOf, self.len * core::mem::size_of::<T>());
+    Vec::from_raw_parts_in(self.ptr, self.len, self.cap, alloc)
+  }
+}
+
 impl<T> Default for StableVec<T>
 where
   T: StableLayout,
@@ -139,3 +604,337 @@ where
     Self::from(Vec::default())
   }
 }
+
+impl<T> FromIterator<T> for StableVec<T>
+where
+  T: StableLayout,
+{
+  /// Collects into a `Vec` and then converts that.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let sv: StableVec<u32> = (1..=3).collect();
+  /// assert_eq!(&sv[..], &[1, 2, 3]);
+  /// # let _ = Vec::from(sv);
+  /// ```
+  #[inline]
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    Self::from(iter.into_iter().collect::<Vec<T>>())
+  }
+}
+
+impl<T> Extend<T> for StableVec<T>
+where
+  T: StableLayout,
+{
+  /// ```rust
+  /// # use chromium::*;
+  /// let mut sv = StableVec::from(vec![1]);
+  /// sv.extend(2..4);
+  /// sv.extend(&[4, 5]);
+  /// assert_eq!(&sv[..], &[1, 2, 3, 4, 5]);
+  /// # let _ = Vec::from(sv);
+  /// ```
+  #[inline]
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    self.with_vec(|vec| vec.extend(iter))
+  }
+}
+
+impl<'a, T> Extend<&'a T> for StableVec<T>
+where
+  T: StableLayout + Copy + 'a,
+{
+  #[inline]
+  fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+    self.with_vec(|vec| vec.extend(iter))
+  }
+}
+
+#[cfg(feature = "subtle")]
+impl<T> subtle::ConstantTimeEq for StableVec<T>
+where
+  T: StableLayout + subtle::ConstantTimeEq,
+{
+  /// Compares the elements in constant time (for a given length).
+  ///
+  /// Vecs of different lengths are unequal, and that part of the check is
+  /// _not_ constant time.
+  #[inline]
+  fn ct_eq(&self, other: &Self) -> subtle::Choice {
+    self.deref().ct_eq(other.deref())
+  }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T> zeroize::Zeroize for StableVec<T>
+where
+  T: StableLayout + zeroize::Zeroize,
+{
+  /// Zeroes the elements _and_ the spare capacity, then sets the length to 0.
+  ///
+  /// A `StableVec` leaks its memory when dropped, so it can't be
+  /// `ZeroizeOnDrop`. If you want the memory scrubbed on drop, wrap the
+  /// `StableVec` in [`Zeroizing`](zeroize::Zeroizing).
+  #[inline]
+  fn zeroize(&mut self) {
+    self.with_vec(|vec| vec.zeroize())
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for StableVec<T>
+where
+  T: StableLayout + serde::Serialize,
+{
+  /// Serializes as a sequence, the same as a `Vec`.
+  #[inline]
+  fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+    self.deref().serialize(s)
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for StableVec<T>
+where
+  T: StableLayout + serde::Deserialize<'de>,
+{
+  /// Deserializes a sequence, the same as a `Vec`.
+  #[inline]
+  fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+    Vec::deserialize(d).map(Self::from)
+  }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T> arbitrary::Arbitrary<'a> for StableVec<T>
+where
+  T: StableLayout + arbitrary::Arbitrary<'a>,
+{
+  /// Makes a `Vec` and then converts it.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// use arbitrary::{Arbitrary, Unstructured};
+  /// let mut u = Unstructured::new(&[1, 2, 3, 4, 5, 6, 7, 8]);
+  /// let sv = StableVec::<u16>::arbitrary(&mut u).unwrap();
+  /// assert!(sv.len() <= 4);
+  /// ```
+  #[inline]
+  fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+    Vec::arbitrary(u).map(Self::from)
+  }
+  #[inline]
+  fn arbitrary_take_rest(
+    u: arbitrary::Unstructured<'a>,
+  ) -> arbitrary::Result<Self> {
+    Vec::arbitrary_take_rest(u).map(Self::from)
+  }
+  #[inline]
+  fn size_hint(depth: usize) -> (usize, Option<usize>) {
+    <Vec<T>>::size_hint(depth)
+  }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A> From<smallvec::SmallVec<A>> for StableVec<A::Item>
+where
+  A: smallvec::Array,
+  A::Item: StableLayout,
+{
+  /// Moves the elements onto the heap (if they aren't already there) and
+  /// takes ownership of that allocation.
+  ///
+  /// A `SmallVec` that has already spilled to the heap gives up its existing
+  /// allocation without copying. One that's storing its elements inline has
+  /// them moved into a new allocation, since inline storage can't outlive the
+  /// `SmallVec`.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// use smallvec::SmallVec;
+  /// let small: SmallVec<[u32; 4]> = SmallVec::from_slice(&[1, 2, 3]);
+  /// let sv = StableVec::from(small);
+  /// let back: SmallVec<[u32; 4]> = SmallVec::from(sv);
+  /// assert_eq!(&back[..], &[1, 2, 3]);
+  /// ```
+  #[inline]
+  fn from(v: smallvec::SmallVec<A>) -> Self {
+    Self::from(v.into_vec())
+  }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A> From<StableVec<A::Item>> for smallvec::SmallVec<A>
+where
+  A: smallvec::Array,
+  A::Item: StableLayout,
+{
+  /// Takes back ownership of the allocation.
+  ///
+  /// The allocation is kept as the `SmallVec`'s heap storage if there are
+  /// more elements than fit inline. Otherwise the elements are moved inline
+  /// and the allocation is freed.
+  #[inline]
+  #[track_caller]
+  fn from(sv: StableVec<A::Item>) -> Self {
+    Self::from_vec(Vec::from(sv))
+  }
+}
+
+#[cfg(feature = "tinyvec")]
+impl<A> From<tinyvec::ArrayVec<A>> for StableVec<A::Item>
+where
+  A: tinyvec::Array,
+  A::Item: StableLayout,
+{
+  /// Moves the elements into a new allocation.
+  #[inline]
+  fn from(v: tinyvec::ArrayVec<A>) -> Self {
+    v.into_iter().collect::<Vec<_>>().into()
+  }
+}
+
+#[cfg(feature = "tinyvec")]
+impl<A> From<tinyvec::TinyVec<A>> for StableVec<A::Item>
+where
+  A: tinyvec::Array,
+  A::Item: StableLayout,
+{
+  /// Moves the elements onto the heap (if they aren't already there) and
+  /// takes ownership of that allocation.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// use tinyvec::{tiny_vec, TinyVec};
+  /// let tiny: TinyVec<[u32; 4]> = tiny_vec!(1, 2, 3);
+  /// let sv = StableVec::from(tiny);
+  /// let back: TinyVec<[u32; 4]> = TinyVec::from(sv);
+  /// assert!(back.is_heap());
+  /// assert_eq!(&back[..], &[1, 2, 3]);
+  /// ```
+  #[inline]
+  fn from(v: tinyvec::TinyVec<A>) -> Self {
+    Self::from(v.into_vec())
+  }
+}
+
+#[cfg(feature = "tinyvec")]
+impl<A> From<StableVec<A::Item>> for tinyvec::TinyVec<A>
+where
+  A: tinyvec::Array,
+  A::Item: StableLayout,
+{
+  /// Takes back ownership of the allocation, as the heap storage of the
+  /// `TinyVec`.
+  #[inline]
+  #[track_caller]
+  fn from(sv: StableVec<A::Item>) -> Self {
+    tinyvec::TinyVec::Heap(Vec::from(sv))
+  }
+}
+
+#[cfg(feature = "widestring")]
+impl From<widestring::U16String> for StableVec<u16> {
+  /// Takes ownership of the string's allocation.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// use widestring::U16String;
+  /// let sv = StableVec::from(U16String::from_str("hi"));
+  /// assert_eq!(&sv[..], &[u16::from(b'h'), u16::from(b'i')]);
+  /// assert_eq!(U16String::from(sv).to_string_lossy(), "hi");
+  /// ```
+  #[inline]
+  fn from(s: widestring::U16String) -> Self {
+    Self::from(s.into_vec())
+  }
+}
+
+#[cfg(feature = "widestring")]
+impl From<StableVec<u16>> for widestring::U16String {
+  /// Takes back ownership of the allocation.
+  #[inline]
+  #[track_caller]
+  fn from(sv: StableVec<u16>) -> Self {
+    Self::from_vec(Vec::from(sv))
+  }
+}
+
+#[cfg(feature = "widestring")]
+impl From<widestring::U16CString> for StableVec<u16> {
+  /// Takes ownership of the string's allocation, including the nul
+  /// terminator.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// # use core::convert::TryFrom;
+  /// use widestring::U16CString;
+  /// let sv = StableVec::from(U16CString::from_str("hi").unwrap());
+  /// assert_eq!(&sv[..], &[u16::from(b'h'), u16::from(b'i'), 0]);
+  /// let back = U16CString::try_from(sv).unwrap();
+  /// assert_eq!(back.to_string_lossy(), "hi");
+  /// ```
+  #[inline]
+  fn from(s: widestring::U16CString) -> Self {
+    Self::from(s.into_vec_with_nul())
+  }
+}
+
+#[cfg(feature = "widestring")]
+impl core::convert::TryFrom<StableVec<u16>> for widestring::U16CString {
+  type Error = widestring::error::ContainsNul<u16>;
+  /// Takes back ownership of the allocation, adding a nul terminator if
+  /// there isn't one.
+  ///
+  /// Fails if there's a nul anywhere other than the last element. The error
+  /// still holds the allocation, so it isn't leaked.
+  #[inline]
+  #[track_caller]
+  fn try_from(
+    sv: StableVec<u16>,
+  ) -> Result<Self, widestring::error::ContainsNul<u16>> {
+    Self::from_vec(Vec::from(sv))
+  }
+}
+
+// Safety: The `StableVec` owns its allocation, which doesn't move or get freed
+// unless a `&mut self` method is called (or the value is converted away).
+#[cfg(feature = "embedded-dma")]
+unsafe impl<T> embedded_dma::ReadBuffer for StableVec<T>
+where
+  T: StableLayout + embedded_dma::Word,
+{
+  type Word = T;
+  #[inline(always)]
+  unsafe fn read_buffer(&self) -> (*const T, usize) {
+    (self.ptr, self.len)
+  }
+}
+
+// Safety: The same as with `ReadBuffer`, and every `Word` type is valid for
+// any bit pattern the DMA might write.
+#[cfg(feature = "embedded-dma")]
+unsafe impl<T> embedded_dma::WriteBuffer for StableVec<T>
+where
+  T: StableLayout + embedded_dma::Word,
+{
+  type Word = T;
+  /// The buffer is the initialized elements, `len` of them. Capacity past the
+  /// length isn't part of the buffer.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// use embedded_dma::{ReadBuffer, WriteBuffer};
+  /// let mut sv = StableVec::from(vec![0_u16; 8]);
+  /// let (ptr, len) = unsafe { sv.write_buffer() };
+  /// assert_eq!(len, 8);
+  /// unsafe { ptr.add(7).write(0xBEEF) }; // pretend to be the DMA
+  /// assert_eq!(unsafe { sv.read_buffer() }.0, ptr as *const u16);
+  /// assert_eq!(Vec::from(sv)[7], 0xBEEF);
+  /// ```
+  #[inline(always)]
+  unsafe fn write_buffer(&mut self) -> (*mut T, usize) {
+    (self.ptr, self.len)
+  }
+}
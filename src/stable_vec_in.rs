@@ -0,0 +1,155 @@
+#![cfg(all(feature = "unsafe_alloc", feature = "nightly_allocator_api"))]
+
+use super::StableLayout;
+use alloc::vec::Vec;
+use core::{
+  alloc::Allocator,
+  fmt::Debug,
+  mem::ManuallyDrop,
+  ops::{Deref, DerefMut},
+  slice,
+};
+
+// General Safety Note: The soundness of the `StableVecIn` type is centered
+// around the fact that the fields are all private, and so *safe rust* must
+// construct values of the type from an existing valid `Vec`. However, because
+// the type is `repr(C)` it can of course be constructed with unsafe rust, or
+// even by foreign code. It is the responsibility of _the other code_ to ensure
+// that the actual fields are valid for being turned into a `Vec`.
+
+/// A struct for the parts of a [`Vec<T, A>`](alloc::vec::Vec) with a stable
+/// layout, including the allocator.
+///
+/// This is like [`StableVec`](crate::StableVec), but it carries its
+/// allocator with it, so there's no question of which allocator the memory
+/// goes back to. The allocator is usually a handle (such as a pointer to an
+/// arena) that both sides of the FFI boundary understand, and so it must be
+/// `StableLayout` too.
+///
+/// ## Unsafety
+///
+/// Because this type is primarily intended to help _unsafe_ Rust we should
+/// discuss the precise guarantees offered:
+/// * **Validity Invariants**
+///   * The data layout is a `*mut T`, `usize`, `usize`, `A`.
+/// * **Soundness Invariants**
+///   * The `*mut T` must point to the start of a valid `Vec<T, A>` allocation.
+///   * The first `usize` must be the correct length of that valid `Vec<T, A>`.
+///   * The second `usize` must be the correct capacity of that valid
+///     `Vec<T, A>`.
+///   * The memory is owned by the `StableVecIn` and was allocated by the `A`
+///     value stored in it.
+///
+/// If you drop a `StableVecIn` without turning it back into a `Vec` then the
+/// memory leaks, and so does the allocator.
+///
+/// If the allocator were a single pointer and your element type were `u8` then
+/// it would be equivalent layout to the following C declaration:
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StableVecIn<u8, &'a Arena>`
+/// typedef struct {
+///   uint8_t *ptr;
+///   uintptr_t len;
+///   uintptr_t cap;
+///   Arena const *alloc;
+/// } StableVecIn_u8;
+/// ```
+#[repr(C)]
+pub struct StableVecIn<T, A>
+where
+  T: StableLayout,
+  A: Allocator + StableLayout,
+{
+  ptr: *mut T,
+  len: usize,
+  cap: usize,
+  alloc: ManuallyDrop<A>,
+}
+
+unsafe impl<T, A> StableLayout for StableVecIn<T, A>
+where
+  T: StableLayout,
+  A: Allocator + StableLayout,
+{
+}
+
+impl<T, A> StableVecIn<T, A>
+where
+  T: StableLayout,
+  A: Allocator + StableLayout,
+{
+  /// The allocator that owns the memory.
+  #[inline(always)]
+  pub fn allocator(&self) -> &A {
+    &self.alloc
+  }
+}
+
+impl<T, A> Deref for StableVecIn<T, A>
+where
+  T: StableLayout,
+  A: Allocator + StableLayout,
+{
+  type Target = [T];
+  #[inline(always)]
+  fn deref(&self) -> &[T] {
+    // Safety: See note at the top of the module.
+    unsafe { slice::from_raw_parts(self.ptr, self.len) }
+  }
+}
+
+impl<T, A> DerefMut for StableVecIn<T, A>
+where
+  T: StableLayout,
+  A: Allocator + StableLayout,
+{
+  #[inline(always)]
+  fn deref_mut(&mut self) -> &mut [T] {
+    // Safety: See note at the top of the module.
+    unsafe { slice::from_raw_parts_mut(self.ptr, self.len) }
+  }
+}
+
+impl<T: Debug, A> Debug for StableVecIn<T, A>
+where
+  T: StableLayout,
+  A: Allocator + StableLayout,
+{
+  /// Debug prints as a slice would.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    Debug::fmt(self.deref(), f)
+  }
+}
+
+impl<T, A> From<Vec<T, A>> for StableVecIn<T, A>
+where
+  T: StableLayout,
+  A: Allocator + StableLayout,
+{
+  fn from(vec: Vec<T, A>) -> Self {
+    let mut md_vec = ManuallyDrop::new(vec);
+    // Safety: The `Vec` is never dropped, so the allocator isn't duplicated.
+    let alloc =
+      ManuallyDrop::new(unsafe { core::ptr::read(md_vec.allocator()) });
+    let cap = md_vec.capacity();
+    let len = md_vec.len();
+    let ptr = md_vec.as_mut_ptr();
+    trace_conversion!(Vec<T, A> => Self, ptr, len);
+    Self { ptr, len, cap, alloc }
+  }
+}
+
+impl<T, A> From<StableVecIn<T, A>> for Vec<T, A>
+where
+  T: StableLayout,
+  A: Allocator + StableLayout,
+{
+  fn from(sv: StableVecIn<T, A>) -> Self {
+    trace_conversion!(StableVecIn<T, A> => Self, sv.ptr, sv.len);
+    let alloc = ManuallyDrop::into_inner(sv.alloc);
+    // Safety: See note at the top of the module.
+    unsafe { Vec::from_raw_parts_in(sv.ptr, sv.len, sv.cap, alloc) }
+  }
+}
@@ -0,0 +1,261 @@
+#![cfg(target_has_atomic = "ptr")]
+
+use core::{
+  cell::UnsafeCell,
+  fmt::Debug,
+  mem::MaybeUninit,
+  slice,
+  sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use super::{SharedSlice, StableLayout, UniqueSlice};
+
+/// A fixed size buffer that can live in a `static`.
+///
+/// This is for `no_std` users without a heap who still want a buffer that can
+/// be lent out, such as to C code or to an interrupt handler. The storage is
+/// inline, and access is controlled by an atomic claim flag, so only one
+/// [`StaticBufClaim`] can exist at a time. The length is stored atomically, so
+/// it can be read without a claim.
+///
+/// Elements are never dropped, so they have to be `Copy`.
+///
+/// ```rust
+/// # use chromium::*;
+/// static BUF: StaticBuf<u16, 8> = StaticBuf::new();
+///
+/// let mut claim = BUF.try_claim().unwrap();
+/// assert!(BUF.try_claim().is_none()); // already claimed
+/// claim.push(1).unwrap();
+/// claim.push(2).unwrap();
+/// assert_eq!(&claim.as_shared()[..], &[1, 2]);
+/// drop(claim);
+///
+/// assert_eq!(BUF.len(), 2);
+/// assert!(BUF.try_claim().is_some());
+/// ```
+///
+/// If your element type were `u8` and `N` were 64 then it would be equivalent
+/// layout to the following C declaration:
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `StaticBuf<u8, 64>`
+/// typedef struct {
+///   uint8_t storage[64];
+///   uintptr_t len; // always access atomically!
+///   uint8_t claimed; // always access atomically!
+/// } StaticBuf_u8_64;
+/// ```
+#[repr(C)]
+pub struct StaticBuf<T, const N: usize>
+where
+  T: StableLayout + Copy,
+{
+  storage: UnsafeCell<[MaybeUninit<T>; N]>,
+  len: AtomicUsize,
+  claimed: AtomicBool,
+}
+
+unsafe impl<T, const N: usize> StableLayout for StaticBuf<T, N> where
+  T: StableLayout + Copy
+{
+}
+
+// Safety: All access to the storage goes through a claim, and only one claim
+// can exist at a time.
+unsafe impl<T, const N: usize> Sync for StaticBuf<T, N> where
+  T: StableLayout + Copy + Send
+{
+}
+
+impl<T, const N: usize> StaticBuf<T, N>
+where
+  T: StableLayout + Copy,
+{
+  /// Makes a new, empty, unclaimed buffer.
+  #[inline]
+  pub const fn new() -> Self {
+    Self {
+      storage: UnsafeCell::new([MaybeUninit::uninit(); N]),
+      len: AtomicUsize::new(0),
+      claimed: AtomicBool::new(false),
+    }
+  }
+
+  /// The number of elements the buffer can hold.
+  #[inline(always)]
+  pub const fn capacity(&self) -> usize {
+    N
+  }
+
+  /// The number of elements in the buffer.
+  ///
+  /// If the buffer is claimed, this can change at any time.
+  #[inline(always)]
+  pub fn len(&self) -> usize {
+    self.len.load(Ordering::Acquire)
+  }
+
+  /// If the buffer is empty.
+  #[inline(always)]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// If the buffer is currently claimed.
+  #[inline(always)]
+  pub fn is_claimed(&self) -> bool {
+    self.claimed.load(Ordering::Relaxed)
+  }
+
+  /// Claims the buffer, or gives `None` if it's already claimed.
+  ///
+  /// This never blocks, so it's fine to call from an interrupt handler.
+  #[inline]
+  pub fn try_claim(&self) -> Option<StaticBufClaim<'_, T, N>> {
+    self
+      .claimed
+      .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+      .ok()
+      .map(|_| StaticBufClaim { buf: self })
+  }
+}
+
+impl<T, const N: usize> Default for StaticBuf<T, N>
+where
+  T: StableLayout + Copy,
+{
+  #[inline(always)]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T, const N: usize> Debug for StaticBuf<T, N>
+where
+  T: StableLayout + Copy,
+{
+  /// Debug prints the length and claim state, not the elements.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_struct("StaticBuf")
+      .field("len", &self.len())
+      .field("cap", &N)
+      .field("claimed", &self.is_claimed())
+      .finish()
+  }
+}
+
+/// Unique access to a [`StaticBuf`], released when dropped.
+pub struct StaticBufClaim<'a, T, const N: usize>
+where
+  T: StableLayout + Copy,
+{
+  buf: &'a StaticBuf<T, N>,
+}
+
+impl<'a, T, const N: usize> StaticBufClaim<'a, T, N>
+where
+  T: StableLayout + Copy,
+{
+  #[inline(always)]
+  fn storage_ptr(&self) -> *mut T {
+    self.buf.storage.get() as *mut T
+  }
+
+  /// The number of elements in the buffer.
+  #[inline(always)]
+  pub fn len(&self) -> usize {
+    self.buf.len.load(Ordering::Relaxed)
+  }
+
+  /// If the buffer is empty.
+  #[inline(always)]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Views the elements.
+  #[inline(always)]
+  pub fn as_shared(&self) -> SharedSlice<'_, T> {
+    // Safety: We hold the claim, and the first `len` elements are initialized.
+    SharedSlice::from(unsafe {
+      slice::from_raw_parts(self.storage_ptr(), self.len())
+    })
+  }
+
+  /// Uniquely views the elements.
+  #[inline(always)]
+  pub fn as_unique(&mut self) -> UniqueSlice<'_, T> {
+    // Safety: We hold the claim, and the first `len` elements are initialized.
+    UniqueSlice::from(unsafe {
+      slice::from_raw_parts_mut(self.storage_ptr(), self.len())
+    })
+  }
+
+  /// The storage past the end of the elements.
+  ///
+  /// Write to the front of this (such as by passing it to C code) and then
+  /// call [`set_len`](Self::set_len).
+  #[inline(always)]
+  pub fn spare_mut(&mut self) -> UniqueSlice<'_, MaybeUninit<T>> {
+    let len = self.len();
+    // Safety: We hold the claim, and `len` is never more than `N`.
+    UniqueSlice::from(unsafe {
+      slice::from_raw_parts_mut(
+        self.storage_ptr().add(len) as *mut MaybeUninit<T>,
+        N - len,
+      )
+    })
+  }
+
+  /// Adds an element to the end, or gives it back if the buffer is full.
+  #[inline]
+  pub fn push(&mut self, value: T) -> Result<(), T> {
+    let len = self.len();
+    if len < N {
+      // Safety: We hold the claim, and `len` is in bounds.
+      unsafe { self.storage_ptr().add(len).write(value) };
+      self.buf.len.store(len + 1, Ordering::Release);
+      Ok(())
+    } else {
+      Err(value)
+    }
+  }
+
+  /// Removes all of the elements.
+  #[inline(always)]
+  pub fn clear(&mut self) {
+    self.buf.len.store(0, Ordering::Release);
+  }
+
+  /// Sets the length.
+  ///
+  /// ## Safety
+  /// * `len` must be no more than `N`.
+  /// * The first `len` elements must be initialized.
+  #[inline(always)]
+  pub unsafe fn set_len(&mut self, len: usize) {
+    self.buf.len.store(len, Ordering::Release);
+  }
+}
+
+impl<'a, T, const N: usize> Drop for StaticBufClaim<'a, T, N>
+where
+  T: StableLayout + Copy,
+{
+  #[inline]
+  fn drop(&mut self) {
+    self.buf.claimed.store(false, Ordering::Release);
+  }
+}
+
+impl<'a, T: Debug, const N: usize> Debug for StaticBufClaim<'a, T, N>
+where
+  T: StableLayout + Copy,
+{
+  /// Debug prints as a slice would.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    Debug::fmt(&*self.as_shared(), f)
+  }
+}
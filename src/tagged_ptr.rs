@@ -0,0 +1,165 @@
+use core::{fmt::Debug, hash::Hash, mem::align_of};
+
+use super::StableLayout;
+
+/// A pointer with a small tag value stored in its low bits.
+///
+/// A pointer to a `T` that's aligned to `align_of::<T>()` always has some low
+/// bits that are zero, so those bits can be used to store a tag of up to
+/// `BITS` bits. It's a common trick in VMs and allocators. `BITS` must be no
+/// more than the number of alignment bits of `T`, which is checked at compile
+/// time.
+///
+/// This is `repr(transparent)` over a `*mut T`, so C sees a plain pointer, and
+/// has to mask off the tag before using it.
+///
+/// ```rust
+/// # use chromium::*;
+/// let mut x = 5_u64;
+/// let p = TaggedPtr::<u64, 3>::new(&mut x, 6).unwrap();
+/// assert_eq!(p.tag(), 6);
+/// assert_eq!(unsafe { *p.ptr() }, 5);
+/// assert!(p.with_tag(8).is_none()); // needs 4 bits
+/// assert_eq!(p.with_tag(1).unwrap().tag(), 1);
+/// ```
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `TaggedPtr<u64, 3>`
+/// typedef uint64_t *TaggedPtr_u64_3; // mask with ~7 before use!
+/// ```
+#[repr(transparent)]
+pub struct TaggedPtr<T, const BITS: u32>
+where
+  T: StableLayout,
+{
+  ptr: *mut T,
+}
+
+unsafe impl<T: StableLayout, const BITS: u32> StableLayout
+  for TaggedPtr<T, BITS>
+{
+}
+
+impl<T, const BITS: u32> TaggedPtr<T, BITS>
+where
+  T: StableLayout,
+{
+  /// The mask of the tag bits.
+  pub const TAG_MASK: usize = {
+    assert!(
+      1_usize.checked_shl(BITS).is_some()
+        && (1_usize << BITS) <= align_of::<T>(),
+      "TaggedPtr: BITS is more than the alignment bits of T"
+    );
+    (1_usize << BITS) - 1
+  };
+
+  /// Tags a pointer.
+  ///
+  /// Gives `None` if the pointer isn't aligned, or if the tag doesn't fit in
+  /// `BITS` bits.
+  #[inline]
+  pub fn new(ptr: *mut T, tag: usize) -> Option<Self> {
+    if (ptr as usize) & Self::TAG_MASK != 0 || tag & !Self::TAG_MASK != 0 {
+      return None;
+    }
+    // The tag bits of `ptr` are all 0, so adding the tag is the same as or-ing
+    // it in. This keeps the pointer's provenance.
+    Some(Self { ptr: ptr.cast::<u8>().wrapping_add(tag).cast::<T>() })
+  }
+
+  /// A null pointer with a tag of 0.
+  #[inline(always)]
+  pub const fn null() -> Self {
+    Self { ptr: core::ptr::null_mut() }
+  }
+
+  /// The pointer, without the tag.
+  #[inline(always)]
+  pub fn ptr(self) -> *mut T {
+    self.ptr.cast::<u8>().wrapping_sub(self.tag()).cast::<T>()
+  }
+
+  /// The tag.
+  #[inline(always)]
+  pub fn tag(self) -> usize {
+    (self.ptr as usize) & Self::TAG_MASK
+  }
+
+  /// The same pointer with a new tag, or `None` if the tag doesn't fit in
+  /// `BITS` bits.
+  #[inline]
+  pub fn with_tag(self, tag: usize) -> Option<Self> {
+    Self::new(self.ptr(), tag)
+  }
+
+  /// The raw, tagged pointer.
+  #[inline(always)]
+  pub const fn to_raw(self) -> *mut T {
+    self.ptr
+  }
+
+  /// Makes a tagged pointer from a raw, tagged pointer.
+  #[inline(always)]
+  pub const fn from_raw(ptr: *mut T) -> Self {
+    Self { ptr }
+  }
+}
+
+impl<T, const BITS: u32> Clone for TaggedPtr<T, BITS>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T, const BITS: u32> Copy for TaggedPtr<T, BITS> where T: StableLayout {}
+
+impl<T, const BITS: u32> PartialEq for TaggedPtr<T, BITS>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn eq(&self, other: &Self) -> bool {
+    self.ptr == other.ptr
+  }
+}
+
+impl<T, const BITS: u32> Eq for TaggedPtr<T, BITS> where T: StableLayout {}
+
+impl<T, const BITS: u32> Hash for TaggedPtr<T, BITS>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+    self.ptr.hash(state)
+  }
+}
+
+impl<T, const BITS: u32> Default for TaggedPtr<T, BITS>
+where
+  T: StableLayout,
+{
+  /// Defaults to null with a tag of 0.
+  #[inline(always)]
+  fn default() -> Self {
+    Self::null()
+  }
+}
+
+impl<T, const BITS: u32> Debug for TaggedPtr<T, BITS>
+where
+  T: StableLayout,
+{
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_struct("TaggedPtr")
+      .field("ptr", &self.ptr())
+      .field("tag", &self.tag())
+      .finish()
+  }
+}
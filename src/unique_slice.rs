@@ -1,11 +1,12 @@
 use core::{
+  borrow::{Borrow, BorrowMut},
   fmt::Debug,
   marker::PhantomData,
   ops::{Deref, DerefMut},
   slice,
 };
 
-use super::StableLayout;
+use super::{SharedSlice, StableLayout, StableStatus};
 
 // General Safety Note: The soundness of the `UniqueSlice` type is centered
 // around the fact that the fields are all private, and so *safe rust* must
@@ -62,6 +63,54 @@ where
 
 unsafe impl<'a, T: StableLayout> StableLayout for UniqueSlice<'a, T> {}
 
+impl<'a, T> UniqueSlice<'a, T>
+where
+  T: StableLayout,
+{
+  /// Copies all elements from a `SharedSlice` into `self`.
+  ///
+  /// This is `copy_from_slice` without having to convert the `SharedSlice`
+  /// first. The rest of the slice methods (`fill`, `swap`, `copy_from_slice`,
+  /// and so on) are all available through `DerefMut`.
+  ///
+  /// ## Panics
+  /// If the two slices have different lengths.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let src = [1, 2, 3];
+  /// let mut dst = [0; 3];
+  /// let mut unique = UniqueSlice::from(&mut dst[..]);
+  /// unique.copy_from_shared(SharedSlice::from(&src[..]));
+  /// unique.swap(0, 2);
+  /// assert_eq!(&unique[..], &[3, 2, 1]);
+  /// ```
+  #[inline]
+  pub fn copy_from_shared(&mut self, src: SharedSlice<'_, T>)
+  where
+    T: Copy,
+  {
+    self.copy_from_slice(&src)
+  }
+
+  /// Copies all elements from a `SharedSlice` into `self`, or gives
+  /// [`StableStatus::INVALID_INPUT`] if the two slices have different lengths.
+  #[inline]
+  pub fn try_copy_from_shared(
+    &mut self, src: SharedSlice<'_, T>,
+  ) -> Result<(), StableStatus>
+  where
+    T: Copy,
+  {
+    if self.len() == src.len() {
+      self.copy_from_slice(&src);
+      Ok(())
+    } else {
+      Err(StableStatus::INVALID_INPUT)
+    }
+  }
+}
+
 impl<'a, T: Debug> Debug for UniqueSlice<'a, T>
 where
   T: StableLayout,
@@ -115,12 +164,78 @@ where
   }
 }
 
+impl<'a, T> AsRef<[T]> for UniqueSlice<'a, T>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn as_ref(&self) -> &[T] {
+    self
+  }
+}
+
+impl<'a, T> AsMut<[T]> for UniqueSlice<'a, T>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn as_mut(&mut self) -> &mut [T] {
+    self
+  }
+}
+
+impl<'a, T> Borrow<[T]> for UniqueSlice<'a, T>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn borrow(&self) -> &[T] {
+    self
+  }
+}
+
+impl<'a, T> BorrowMut<[T]> for UniqueSlice<'a, T>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn borrow_mut(&mut self) -> &mut [T] {
+    self
+  }
+}
+
+impl<'b, 'a, T> IntoIterator for &'b UniqueSlice<'a, T>
+where
+  T: StableLayout,
+{
+  type Item = &'b T;
+  type IntoIter = slice::Iter<'b, T>;
+  #[inline(always)]
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'b, 'a, T> IntoIterator for &'b mut UniqueSlice<'a, T>
+where
+  T: StableLayout,
+{
+  type Item = &'b mut T;
+  type IntoIter = slice::IterMut<'b, T>;
+  #[inline(always)]
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter_mut()
+  }
+}
+
 impl<'a, T> From<&'a mut [T]> for UniqueSlice<'a, T>
 where
   T: StableLayout,
 {
   #[inline(always)]
   fn from(sli: &'a mut [T]) -> Self {
+    trace_conversion!(&'a mut [T] => Self, sli.as_ptr(), sli.len());
+    count_conversion!(UniqueSlice, Into, core::mem::size_of_val(sli));
     let life = PhantomData;
     let len = sli.len();
     let ptr = sli.as_mut_ptr();
@@ -134,7 +249,272 @@ where
 {
   #[inline(always)]
   fn from(unique: UniqueSlice<'a, T>) -> Self {
+    trace_conversion!(UniqueSlice<'a, T> => Self, unique.ptr, unique.len);
+    count_conversion!(
+      UniqueSlice,
+      OutOf,
+      unique.len * core::mem::size_of::<T>()
+    );
     // Safety: See note at the top of the module.
     unsafe { slice::from_raw_parts_mut(unique.ptr, unique.len) }
   }
 }
+
+#[cfg(feature = "bytemuck")]
+impl<'a, T> UniqueSlice<'a, T>
+where
+  T: StableLayout + bytemuck::Pod,
+{
+  /// Views the slice as a slice of some other `Pod` type.
+  ///
+  /// This fails if the output alignment isn't met, or if the total size of
+  /// the slice isn't a multiple of the output element size.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let mut data = [0_u32; 2];
+  /// let unique = UniqueSlice::from(&mut data[..]);
+  /// let mut bytes: UniqueSlice<u8> = unique.try_cast_mut().unwrap();
+  /// bytes.fill(0xFF);
+  /// assert_eq!(data, [u32::MAX; 2]);
+  /// ```
+  #[inline]
+  pub fn try_cast_mut<U>(
+    self,
+  ) -> Result<UniqueSlice<'a, U>, bytemuck::PodCastError>
+  where
+    U: StableLayout + bytemuck::Pod,
+  {
+    let sli: &'a mut [T] = self.into();
+    bytemuck::try_cast_slice_mut(sli).map(UniqueSlice::from)
+  }
+}
+
+#[cfg(feature = "subtle")]
+impl<'a, T> subtle::ConstantTimeEq for UniqueSlice<'a, T>
+where
+  T: StableLayout + subtle::ConstantTimeEq,
+{
+  /// Compares the elements in constant time (for a given length).
+  ///
+  /// Slices of different lengths are unequal, and that part of the check is
+  /// _not_ constant time.
+  #[inline]
+  fn ct_eq(&self, other: &Self) -> subtle::Choice {
+    self.deref().ct_eq(other.deref())
+  }
+}
+
+#[cfg(feature = "zeroize")]
+impl<'a, T> zeroize::Zeroize for UniqueSlice<'a, T>
+where
+  T: StableLayout + zeroize::DefaultIsZeroes,
+{
+  /// Zeroes all of the elements of the slice.
+  #[inline]
+  fn zeroize(&mut self) {
+    self.deref_mut().zeroize()
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'a, T> serde::Serialize for UniqueSlice<'a, T>
+where
+  T: StableLayout + serde::Serialize,
+{
+  /// Serializes as a sequence, the same as a slice.
+  #[inline]
+  fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+    self.deref().serialize(s)
+  }
+}
+
+#[cfg(feature = "heapless")]
+impl<'a, T, const N: usize> From<&'a mut heapless::Vec<T, N>>
+  for UniqueSlice<'a, T>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn from(v: &'a mut heapless::Vec<T, N>) -> Self {
+    Self::from(v.as_mut_slice())
+  }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<'a, T, const N: usize> From<&'a mut arrayvec::ArrayVec<T, N>>
+  for UniqueSlice<'a, T>
+where
+  T: StableLayout,
+{
+  #[inline(always)]
+  fn from(v: &'a mut arrayvec::ArrayVec<T, N>) -> Self {
+    Self::from(v.as_mut_slice())
+  }
+}
+
+#[cfg(feature = "smallvec")]
+impl<'a, A> From<&'a mut smallvec::SmallVec<A>> for UniqueSlice<'a, A::Item>
+where
+  A: smallvec::Array,
+  A::Item: StableLayout,
+{
+  #[inline(always)]
+  fn from(v: &'a mut smallvec::SmallVec<A>) -> Self {
+    Self::from(v.as_mut_slice())
+  }
+}
+
+#[cfg(feature = "tinyvec")]
+impl<'a, A> From<&'a mut tinyvec::ArrayVec<A>> for UniqueSlice<'a, A::Item>
+where
+  A: tinyvec::Array,
+  A::Item: StableLayout,
+{
+  #[inline(always)]
+  fn from(v: &'a mut tinyvec::ArrayVec<A>) -> Self {
+    Self::from(v.as_mut_slice())
+  }
+}
+
+#[cfg(all(feature = "tinyvec", feature = "unsafe_alloc"))]
+impl<'a, A> From<&'a mut tinyvec::TinyVec<A>> for UniqueSlice<'a, A::Item>
+where
+  A: tinyvec::Array,
+  A::Item: StableLayout,
+{
+  #[inline(always)]
+  fn from(v: &'a mut tinyvec::TinyVec<A>) -> Self {
+    Self::from(v.as_mut_slice())
+  }
+}
+
+#[cfg(feature = "widestring")]
+impl<'a> From<&'a mut widestring::U16Str> for UniqueSlice<'a, u16> {
+  #[inline(always)]
+  fn from(s: &'a mut widestring::U16Str) -> Self {
+    Self::from(s.as_mut_slice())
+  }
+}
+
+#[cfg(feature = "widestring")]
+impl<'a> From<UniqueSlice<'a, u16>> for &'a mut widestring::U16Str {
+  #[inline(always)]
+  fn from(unique: UniqueSlice<'a, u16>) -> Self {
+    let sli: &'a mut [u16] = unique.into();
+    widestring::U16Str::from_slice_mut(sli)
+  }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, T> rayon::iter::IntoParallelIterator for UniqueSlice<'a, T>
+where
+  T: StableLayout + Send,
+{
+  type Item = &'a mut T;
+  type Iter = rayon::slice::IterMut<'a, T>;
+  /// Iterates over the elements in parallel.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// use rayon::prelude::*;
+  /// let mut data = [1_u32; 64];
+  /// let mut unique = UniqueSlice::from(&mut data[..]);
+  /// unique.par_iter_mut().for_each(|x| *x *= 2);
+  /// unique.par_chunks_mut(8).for_each(|c| c[0] = 0);
+  /// assert_eq!(unique.par_iter().sum::<u32>(), 2 * 56);
+  /// unique.into_par_iter().for_each(|x| *x += 1);
+  /// assert_eq!(&data[..3], &[1, 3, 3]);
+  /// ```
+  #[inline(always)]
+  fn into_par_iter(self) -> Self::Iter {
+    let sli: &'a mut [T] = self.into();
+    sli.into_par_iter()
+  }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, 'b, T> rayon::iter::IntoParallelIterator for &'b UniqueSlice<'a, T>
+where
+  T: StableLayout + Sync,
+{
+  type Item = &'b T;
+  type Iter = rayon::slice::Iter<'b, T>;
+  #[inline(always)]
+  fn into_par_iter(self) -> Self::Iter {
+    self.deref().into_par_iter()
+  }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, 'b, T> rayon::iter::IntoParallelIterator for &'b mut UniqueSlice<'a, T>
+where
+  T: StableLayout + Send,
+{
+  type Item = &'b mut T;
+  type Iter = rayon::slice::IterMut<'b, T>;
+  #[inline(always)]
+  fn into_par_iter(self) -> Self::Iter {
+    self.deref_mut().into_par_iter()
+  }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, T> rayon::slice::ParallelSlice<T> for UniqueSlice<'a, T>
+where
+  T: StableLayout + Sync,
+{
+  #[inline(always)]
+  fn as_parallel_slice(&self) -> &[T] {
+    self
+  }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, T> rayon::slice::ParallelSliceMut<T> for UniqueSlice<'a, T>
+where
+  T: StableLayout + Send,
+{
+  #[inline(always)]
+  fn as_parallel_slice_mut(&mut self) -> &mut [T] {
+    self
+  }
+}
+
+// Safety: The slice is `'static`, so the memory can't be freed during the
+// transfer, and the pointer and length never change.
+#[cfg(feature = "embedded-dma")]
+unsafe impl<T> embedded_dma::ReadBuffer for UniqueSlice<'static, T>
+where
+  T: StableLayout + embedded_dma::Word,
+{
+  type Word = T;
+  #[inline(always)]
+  unsafe fn read_buffer(&self) -> (*const T, usize) {
+    (self.ptr, self.len)
+  }
+}
+
+// Safety: The same as with `ReadBuffer`, and every `Word` type is valid for
+// any bit pattern the DMA might write.
+#[cfg(feature = "embedded-dma")]
+unsafe impl<T> embedded_dma::WriteBuffer for UniqueSlice<'static, T>
+where
+  T: StableLayout + embedded_dma::Word,
+{
+  type Word = T;
+  /// ```rust
+  /// # use chromium::*;
+  /// use embedded_dma::WriteBuffer;
+  /// let buf: &'static mut [u8] = Box::leak(Box::new([0_u8; 16]));
+  /// let mut unique = UniqueSlice::from(buf);
+  /// let (ptr, len) = unsafe { unique.write_buffer() };
+  /// assert_eq!(len, 16);
+  /// unsafe { ptr.write_bytes(0xAB, len) }; // pretend to be the DMA
+  /// assert!(unique.iter().all(|&b| b == 0xAB));
+  /// ```
+  #[inline(always)]
+  unsafe fn write_buffer(&mut self) -> (*mut T, usize) {
+    (self.ptr, self.len)
+  }
+}
@@ -1,5 +1,6 @@
 use core::{
-  fmt::Debug,
+  borrow::{Borrow, BorrowMut},
+  fmt::{Debug, Display},
   marker::PhantomData,
   ops::{Deref, DerefMut},
   slice, str,
@@ -59,6 +60,13 @@ impl<'a> Debug for UniqueStr<'a> {
   }
 }
 
+impl<'a> Display for UniqueStr<'a> {
+  /// Display prints as a `str` would.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    Display::fmt(self.deref(), f)
+  }
+}
+
 impl<'a> Clone for UniqueStr<'a> {
   #[inline(always)]
   fn clone(&self) -> Self {
@@ -108,9 +116,46 @@ impl<'a> DerefMut for UniqueStr<'a> {
   }
 }
 
+impl<'a> AsRef<str> for UniqueStr<'a> {
+  #[inline(always)]
+  fn as_ref(&self) -> &str {
+    self
+  }
+}
+
+impl<'a> AsRef<[u8]> for UniqueStr<'a> {
+  #[inline(always)]
+  fn as_ref(&self) -> &[u8] {
+    self.as_bytes()
+  }
+}
+
+impl<'a> AsMut<str> for UniqueStr<'a> {
+  #[inline(always)]
+  fn as_mut(&mut self) -> &mut str {
+    self
+  }
+}
+
+impl<'a> Borrow<str> for UniqueStr<'a> {
+  #[inline(always)]
+  fn borrow(&self) -> &str {
+    self
+  }
+}
+
+impl<'a> BorrowMut<str> for UniqueStr<'a> {
+  #[inline(always)]
+  fn borrow_mut(&mut self) -> &mut str {
+    self
+  }
+}
+
 impl<'a> From<&'a mut str> for UniqueStr<'a> {
   #[inline(always)]
   fn from(s: &'a mut str) -> Self {
+    trace_conversion!(&'a mut str => Self, s.as_ptr(), s.len());
+    count_conversion!(UniqueStr, Into, s.len());
     let life = PhantomData;
     let len = s.len();
     let ptr = s.as_mut_ptr();
@@ -121,6 +166,8 @@ impl<'a> From<&'a mut str> for UniqueStr<'a> {
 impl<'a> From<UniqueStr<'a>> for &'a mut str {
   #[inline(always)]
   fn from(unique: UniqueStr<'a>) -> Self {
+    trace_conversion!(UniqueStr<'a> => Self, unique.ptr, unique.len);
+    count_conversion!(UniqueStr, OutOf, unique.len);
     // Safety: See note at the top of the module.
     unsafe {
       str::from_utf8_unchecked_mut(slice::from_raw_parts_mut(
@@ -129,3 +176,29 @@ impl<'a> From<UniqueStr<'a>> for &'a mut str {
     }
   }
 }
+
+#[cfg(feature = "zeroize")]
+impl<'a> zeroize::Zeroize for UniqueStr<'a> {
+  /// Zeroes all of the bytes of the string (which is still valid UTF-8).
+  #[inline]
+  fn zeroize(&mut self) {
+    self.deref_mut().zeroize()
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for UniqueStr<'a> {
+  /// Serializes as a string.
+  #[inline]
+  fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(self)
+  }
+}
+
+#[cfg(feature = "heapless")]
+impl<'a, const N: usize> From<&'a mut heapless::String<N>> for UniqueStr<'a> {
+  #[inline(always)]
+  fn from(s: &'a mut heapless::String<N>) -> Self {
+    Self::from(s.as_mut_str())
+  }
+}
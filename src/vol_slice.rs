@@ -0,0 +1,299 @@
+use core::{fmt::Debug, marker::PhantomData};
+
+use super::{StableLayout, StableStatus};
+
+// General Safety Note: The soundness of the volatile slice types is centered
+// around the fact that the fields are all private, and so *safe rust* must
+// construct values of the types from an existing valid slice, and only unsafe
+// code can point them at MMIO. Because the types are `repr(C)` they can also
+// be constructed by foreign code. It is the responsibility of _the other code_
+// to ensure that the fields describe memory that's valid for volatile access.
+
+/// A struct for **shared** volatile memory blocks with a stable layout.
+///
+/// This is a pointer and length like a [`SharedSlice`](crate::SharedSlice),
+/// except that every access is a volatile read, and the elements are never
+/// borrowed as Rust references. Use it to describe memory mapped registers or
+/// buffers that are shared with a C driver, where the hardware (or the other
+/// side) can change the values at any time.
+///
+/// ## Unsafety
+///
+/// Because this type is primarily intended to help _unsafe_ Rust we should
+/// discuss the precise guarantees offered:
+/// * **Validity Invariants**
+///   * The data layout is a `*const T` and then a `usize`.
+/// * **Soundness Invariants**
+///   * For as long as the `VolSharedSlice` exists, each of the `usize`
+///     elements starting at the `*const T` must be valid for volatile reads of
+///     `T`, and each read must give a valid `T`.
+///
+/// If your element type were `u32` then it would be equivalent layout to the
+/// following C declaration:
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `VolSharedSlice<'a, u32>`
+/// typedef struct {
+///   volatile uint32_t const *ptr;
+///   uintptr_t len;
+/// } VolSharedSlice_u32;
+/// ```
+#[repr(C)]
+pub struct VolSharedSlice<'a, T>
+where
+  T: StableLayout + Copy,
+{
+  ptr: *const T,
+  len: usize,
+  life: PhantomData<&'a [T]>,
+}
+
+unsafe impl<'a, T> StableLayout for VolSharedSlice<'a, T> where
+  T: StableLayout + Copy
+{
+}
+
+impl<'a, T> VolSharedSlice<'a, T>
+where
+  T: StableLayout + Copy,
+{
+  /// Makes a volatile slice over some raw memory, such as an MMIO block.
+  ///
+  /// ## Safety
+  /// * The memory must uphold the invariants listed on the type for all of
+  ///   `'a`.
+  #[inline(always)]
+  pub const unsafe fn from_raw_parts(ptr: *const T, len: usize) -> Self {
+    Self { ptr, len, life: PhantomData }
+  }
+
+  /// The number of elements.
+  #[inline(always)]
+  pub const fn len(&self) -> usize {
+    self.len
+  }
+
+  /// If there are no elements.
+  #[inline(always)]
+  pub const fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// The pointer to the start of the block.
+  #[inline(always)]
+  pub const fn as_ptr(&self) -> *const T {
+    self.ptr
+  }
+
+  /// Does a volatile read of the element at `index`, or gives `None` if the
+  /// index is out of bounds.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let regs = [1_u32, 2, 3];
+  /// let vol = VolSharedSlice::from(&regs[..]);
+  /// assert_eq!(vol.read(1), Some(2));
+  /// assert_eq!(vol.read(3), None);
+  /// assert!(vol.iter().eq(regs.iter().copied()));
+  /// ```
+  #[inline]
+  pub fn read(&self, index: usize) -> Option<T> {
+    if index < self.len {
+      // Safety: See note at the top of the module.
+      Some(unsafe { self.ptr.add(index).read_volatile() })
+    } else {
+      None
+    }
+  }
+
+  /// Iterates over the elements, doing one volatile read for each.
+  #[inline]
+  pub fn iter(&self) -> impl Iterator<Item = T> + 'a {
+    let this = *self;
+    // Safety: See note at the top of the module.
+    (0..self.len).map(move |i| unsafe { this.ptr.add(i).read_volatile() })
+  }
+}
+
+impl<'a, T> Clone for VolSharedSlice<'a, T>
+where
+  T: StableLayout + Copy,
+{
+  #[inline(always)]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<'a, T> Copy for VolSharedSlice<'a, T> where T: StableLayout + Copy {}
+
+impl<'a, T> Debug for VolSharedSlice<'a, T>
+where
+  T: StableLayout + Copy,
+{
+  /// Debug prints the address and length, without reading the memory.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_struct("VolSharedSlice")
+      .field("ptr", &self.ptr)
+      .field("len", &self.len)
+      .finish()
+  }
+}
+
+impl<'a, T> From<&'a [T]> for VolSharedSlice<'a, T>
+where
+  T: StableLayout + Copy,
+{
+  #[inline(always)]
+  fn from(sli: &'a [T]) -> Self {
+    Self { ptr: sli.as_ptr(), len: sli.len(), life: PhantomData }
+  }
+}
+
+/// A struct for **unique** volatile memory blocks with a stable layout.
+///
+/// This is the read-write partner of [`VolSharedSlice`]. Every access is a
+/// volatile read or write, and the elements are never borrowed as Rust
+/// references.
+///
+/// ## Unsafety
+///
+/// Because this type is primarily intended to help _unsafe_ Rust we should
+/// discuss the precise guarantees offered:
+/// * **Validity Invariants**
+///   * The data layout is a `*mut T` and then a `usize`.
+/// * **Soundness Invariants**
+///   * For as long as the `VolUniqueSlice` exists, each of the `usize`
+///     elements starting at the `*mut T` must be valid for volatile reads and
+///     writes of `T`, and each read must give a valid `T`.
+///   * No other Rust code may access the memory through a reference during
+///     that time.
+///
+/// If your element type were `u32` then it would be equivalent layout to the
+/// following C declaration:
+///
+/// ```c
+/// #include <stdint.h>
+/// // Identical layout to `VolUniqueSlice<'a, u32>`
+/// typedef struct {
+///   volatile uint32_t *ptr;
+///   uintptr_t len;
+/// } VolUniqueSlice_u32;
+/// ```
+#[repr(C)]
+pub struct VolUniqueSlice<'a, T>
+where
+  T: StableLayout + Copy,
+{
+  ptr: *mut T,
+  len: usize,
+  life: PhantomData<&'a mut [T]>,
+}
+
+unsafe impl<'a, T> StableLayout for VolUniqueSlice<'a, T> where
+  T: StableLayout + Copy
+{
+}
+
+impl<'a, T> VolUniqueSlice<'a, T>
+where
+  T: StableLayout + Copy,
+{
+  /// Makes a volatile slice over some raw memory, such as an MMIO block.
+  ///
+  /// ## Safety
+  /// * The memory must uphold the invariants listed on the type for all of
+  ///   `'a`.
+  #[inline(always)]
+  pub const unsafe fn from_raw_parts(ptr: *mut T, len: usize) -> Self {
+    Self { ptr, len, life: PhantomData }
+  }
+
+  /// The number of elements.
+  #[inline(always)]
+  pub const fn len(&self) -> usize {
+    self.len
+  }
+
+  /// If there are no elements.
+  #[inline(always)]
+  pub const fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// The pointer to the start of the block.
+  #[inline(always)]
+  pub const fn as_mut_ptr(&self) -> *mut T {
+    self.ptr
+  }
+
+  /// Views the block as read-only, for as long as this is borrowed.
+  #[inline(always)]
+  pub fn as_shared(&self) -> VolSharedSlice<'_, T> {
+    VolSharedSlice { ptr: self.ptr, len: self.len, life: PhantomData }
+  }
+
+  /// Does a volatile read of the element at `index`, or gives `None` if the
+  /// index is out of bounds.
+  #[inline(always)]
+  pub fn read(&self, index: usize) -> Option<T> {
+    self.as_shared().read(index)
+  }
+
+  /// Does a volatile write of the element at `index`, or gives
+  /// [`StableStatus::INVALID_INPUT`] if the index is out of bounds.
+  ///
+  /// ```rust
+  /// # use chromium::*;
+  /// let mut regs = [0_u32; 2];
+  /// let mut vol = VolUniqueSlice::from(&mut regs[..]);
+  /// vol.write(1, 5).unwrap();
+  /// assert_eq!(vol.read(1), Some(5));
+  /// assert!(vol.write(2, 5).is_err());
+  /// vol.fill(7);
+  /// assert_eq!(regs, [7, 7]);
+  /// ```
+  #[inline]
+  pub fn write(&mut self, index: usize, value: T) -> Result<(), StableStatus> {
+    if index < self.len {
+      // Safety: See note at the top of the module.
+      unsafe { self.ptr.add(index).write_volatile(value) };
+      Ok(())
+    } else {
+      Err(StableStatus::INVALID_INPUT)
+    }
+  }
+
+  /// Does a volatile write of `value` to every element, in order.
+  #[inline]
+  pub fn fill(&mut self, value: T) {
+    for i in 0..self.len {
+      // Safety: See note at the top of the module.
+      unsafe { self.ptr.add(i).write_volatile(value) };
+    }
+  }
+}
+
+impl<'a, T> Debug for VolUniqueSlice<'a, T>
+where
+  T: StableLayout + Copy,
+{
+  /// Debug prints the address and length, without reading the memory.
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_struct("VolUniqueSlice")
+      .field("ptr", &self.ptr)
+      .field("len", &self.len)
+      .finish()
+  }
+}
+
+impl<'a, T> From<&'a mut [T]> for VolUniqueSlice<'a, T>
+where
+  T: StableLayout + Copy,
+{
+  #[inline(always)]
+  fn from(sli: &'a mut [T]) -> Self {
+    Self { ptr: sli.as_mut_ptr(), len: sli.len(), life: PhantomData }
+  }
+}
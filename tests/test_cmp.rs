@@ -0,0 +1,107 @@
+use chromium::*;
+use std::collections::HashSet;
+
+#[test]
+fn test_slice_eq_across_types() {
+  let a = [1_u8, 2, 3];
+  let mut b = [1_u8, 2, 3];
+  let shared = SharedSlice::from(&a[..]);
+  let unique = UniqueSlice::from(&mut b[..]);
+  assert_eq!(shared, unique);
+  assert_eq!(unique, shared);
+  assert_ne!(shared, SharedSlice::from(&a[..2]));
+}
+
+#[test]
+fn test_str_eq_across_types() {
+  let mut buf = String::from("hello");
+  let shared = SharedStr::from("hello");
+  let unique = UniqueStr::from(buf.as_mut_str());
+  assert_eq!(shared, unique);
+  assert_ne!(shared, SharedStr::from("help"));
+}
+
+#[test]
+fn test_hash_matches_contents() {
+  let words = ["a", "b", "a"];
+  let set: HashSet<SharedStr<'_>> =
+    words.iter().map(|w| SharedStr::from(*w)).collect();
+  assert_eq!(set.len(), 2);
+  assert!(set.contains(&SharedStr::from("b")));
+}
+
+#[test]
+#[cfg(feature = "unsafe_alloc")]
+fn test_owned_eq() {
+  let data = [1_i32, 2, 3];
+  let sv = StableVec::from(data.to_vec());
+  assert_eq!(sv, SharedSlice::from(&data[..]));
+  assert_eq!(SharedSlice::from(&data[..]), sv);
+  let _ = Vec::from(sv);
+  let s = StableString::from(String::from("hi"));
+  assert_eq!(s, SharedStr::from("hi"));
+  let _ = String::from(s);
+}
+
+#[test]
+fn test_ord_matches_contents() {
+  let mut words: Vec<SharedStr<'_>> =
+    ["pear", "apple", "fig"].iter().map(|w| SharedStr::from(*w)).collect();
+  words.sort();
+  assert!(words.iter().map(|w| &**w).eq(["apple", "fig", "pear"]));
+  assert_eq!(words.binary_search(&SharedStr::from("fig")), Ok(1));
+  let a = [1, 2, 3];
+  let b = [1, 3];
+  assert!(SharedSlice::from(&a[..]) < SharedSlice::from(&b[..]));
+  let set: std::collections::BTreeSet<SharedSlice<'_, i32>> =
+    [SharedSlice::from(&b[..]), SharedSlice::from(&a[..])].into();
+  assert_eq!(set.iter().next().map(|s| &s[..]), Some(&a[..]));
+}
+
+#[test]
+fn test_eq_native() {
+  let a = [1, 2, 3];
+  let shared = SharedSlice::from(&a[..]);
+  assert_eq!(shared, &[1, 2, 3]);
+  assert_eq!(shared, [1, 2, 3]);
+  assert_eq!(shared, &a[..]);
+  assert_eq!(&a[..], shared);
+  let s = SharedStr::from("hello");
+  assert_eq!(s, "hello");
+  assert_eq!("hello", s);
+  assert_ne!(*"help", s);
+}
+
+#[test]
+#[cfg(feature = "unsafe_alloc")]
+fn test_owned_eq_native() {
+  let a = [1, 2, 3];
+  let shared = SharedSlice::from(&a[..]);
+  assert_eq!(shared, vec![1, 2, 3]);
+  assert_ne!(vec![1, 2], shared);
+  assert_eq!(SharedStr::from("hello"), String::from("hello"));
+  let sv = StableVec::from(vec![1_u8, 2]);
+  assert_eq!(sv, [1, 2]);
+  assert_eq!(vec![1, 2], sv);
+  let _ = Vec::from(sv);
+  let s = StableString::from(String::from("hi"));
+  assert_eq!(s, "hi");
+  assert_eq!(String::from("hi"), s);
+  let _ = String::from(s);
+}
+
+#[test]
+fn test_borrow_lookups() {
+  let set: HashSet<SharedStr<'_>> =
+    ["a", "b"].iter().map(|w| SharedStr::from(*w)).collect();
+  assert!(set.contains("b"));
+  assert!(!set.contains("c"));
+  let a = [1, 2];
+  let tree: std::collections::BTreeSet<SharedSlice<'_, i32>> =
+    core::iter::once(SharedSlice::from(&a[..])).collect();
+  assert!(tree.contains(&[1, 2][..]));
+  fn byte_len(x: impl AsRef<[u8]>) -> usize {
+    x.as_ref().len()
+  }
+  assert_eq!(byte_len(SharedStr::from("héllo")), 6);
+}
@@ -0,0 +1,27 @@
+//! The counters are process wide, so these checks live in their own test
+//! binary where no other test is converting values at the same time.
+#![cfg(all(feature = "conversion_stats", feature = "unsafe_alloc"))]
+
+use chromium::*;
+
+#[test]
+fn test_round_trips_are_not_counted() {
+  let mut sv = StableVec::from(vec![1, 2, 3, 4]);
+  let mut ss = StableString::from(String::from("hello"));
+  let before = ConversionStats::snapshot();
+
+  sv.retain(|&x| x % 2 == 0);
+  ss.try_reserve(10).unwrap();
+  let _ = StableVec::<u8>::default().take_vec();
+  let _ = StableString::default().take_string();
+
+  let after = ConversionStats::snapshot();
+  assert_eq!(after.owned_outstanding, before.owned_outstanding);
+  assert_eq!(after.stable_vec.into, before.stable_vec.into + 1);
+  assert_eq!(after.stable_vec.out_of, before.stable_vec.out_of + 1);
+
+  assert_eq!(Vec::from(sv), vec![2, 4]);
+  assert_eq!(String::from(ss), "hello");
+  let end = ConversionStats::snapshot();
+  assert_eq!(end.owned_outstanding, before.owned_outstanding - 2);
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "derive")]
+#![allow(bad_style)]
+
+use chromium::*;
+
+#[derive(StableLayout)]
+#[repr(C)]
+struct Point {
+  x: f32,
+  y: f32,
+}
+
+#[derive(StableLayout)]
+#[repr(transparent)]
+struct Meters(f64);
+
+#[derive(StableLayout)]
+#[repr(C, align(16))]
+struct Tagged<'a, T: StableLayout> {
+  tag: u32,
+  data: SharedSlice<'a, T>,
+}
+
+#[derive(StableLayout)]
+#[repr(C)]
+struct Empty;
+
+fn assert_stable_layout<T: StableLayout>() {}
+
+#[test]
+fn test_derive_StableLayout() {
+  assert_stable_layout::<Point>();
+  assert_stable_layout::<Meters>();
+  assert_stable_layout::<Tagged<'static, Point>>();
+  assert_stable_layout::<Empty>();
+  let p = Point { x: 1.0, y: 2.0 };
+  let _ = (p.x, p.y, Meters(1.0).0);
+  let t = Tagged { tag: 1, data: SharedSlice::<'_, u8>::default() };
+  assert_eq!(t.tag, 1);
+  assert_eq!(t.data.len(), 0);
+}
@@ -0,0 +1,49 @@
+use chromium::*;
+
+#[test]
+fn test_ref_into_iter() {
+  let a = [1, 2, 3];
+  let shared = SharedSlice::from(&a[..]);
+  let mut total = 0;
+  for x in &shared {
+    total += *x;
+  }
+  assert_eq!(total, 6);
+
+  let mut b = [1, 2, 3];
+  let mut unique = UniqueSlice::from(&mut b[..]);
+  for x in &mut unique {
+    *x *= 10;
+  }
+  assert!((&unique).into_iter().eq(&[10, 20, 30]));
+  assert_eq!(b, [10, 20, 30]);
+}
+
+#[test]
+#[cfg(feature = "unsafe_alloc")]
+fn test_stable_vec_ref_into_iter() {
+  let mut sv = StableVec::from(vec![1, 2, 3]);
+  for x in &mut sv {
+    *x += 1;
+  }
+  assert_eq!((&sv).into_iter().sum::<i32>(), 9);
+  let _ = Vec::from(sv);
+}
+
+#[test]
+#[cfg(feature = "unsafe_alloc")]
+fn test_consuming_into_iter() {
+  let words = vec![String::from("a"), String::from("b")];
+  let sv: StableVec<StableString> = StableVec::from(
+    words.into_iter().map(StableString::from).collect::<Vec<_>>(),
+  );
+  let joined: String = sv.into_iter().map(String::from).collect();
+  assert_eq!(joined, "ab");
+  let ss = StableString::from(String::from("añb"));
+  let mut chars = ss.into_chars();
+  assert_eq!(chars.next_back(), Some('b'));
+  assert_eq!(chars.next(), Some('a'));
+  assert_eq!(chars.next_back(), Some('ñ'));
+  assert_eq!(chars.next(), None);
+  assert_eq!(chars.next_back(), None);
+}
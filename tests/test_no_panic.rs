@@ -0,0 +1,122 @@
+//! Checks that conversions and accessors can't panic.
+//!
+//! Each check is wrapped in a guard that references a symbol which doesn't
+//! exist, but only from the guard's unwinding path. If the optimizer can prove
+//! that nothing in the check unwinds then the reference goes away, and if it
+//! can't then linking fails with the name of the check. The arguments are put
+//! through `black_box`, so the checks hold for any input. This only works with
+//! optimizations, so run it with:
+//!
+//! `cargo test --release --features "no_panic unsafe_alloc" --test test_no_panic`
+#![cfg(all(feature = "no_panic", not(debug_assertions)))]
+#![allow(bad_style)]
+
+use chromium::*;
+
+macro_rules! no_panic {
+  ($(fn $name:ident($($arg:ident: $t:ty),* $(,)?) -> $r:ty $body:block)*) => {
+    $(
+      #[inline(never)]
+      #[allow(clippy::redundant_closure_call)]
+      fn $name($($arg: $t),*) -> $r {
+        struct Guard;
+        impl Drop for Guard {
+          fn drop(&mut self) {
+            extern "C" {
+              #[link_name = concat!(
+                "\n\nERROR: `", stringify!($name), "` can panic\n\n"
+              )]
+              fn trigger() -> !;
+            }
+            unsafe { trigger() }
+          }
+        }
+        let guard = Guard;
+        // Hide the arguments, so the check is for _any_ input.
+        $( let $arg = core::hint::black_box($arg); )*
+        // A closure, so that a `?` in the body can't skip the `forget`.
+        let out = (move || $body)();
+        core::mem::forget(guard);
+        out
+      }
+    )*
+  };
+}
+
+no_panic! {
+  fn shared_slice_round_trip(s: &[u32]) -> &[u32] {
+    <&[u32]>::from(SharedSlice::from(s))
+  }
+  fn shared_slice_windows(s: &[u32], n: usize) -> usize {
+    SharedSlice::from(s).try_windows(n).map(|w| w.count()).unwrap_or(0)
+  }
+  fn unique_slice_round_trip(s: &mut [u32]) -> &mut [u32] {
+    <&mut [u32]>::from(UniqueSlice::from(s))
+  }
+  fn unique_slice_copy(d: &mut [u32], s: &[u32]) -> bool {
+    UniqueSlice::from(d).try_copy_from_shared(SharedSlice::from(s)).is_ok()
+  }
+  fn shared_str_round_trip(s: &str) -> &str {
+    <&str>::from(SharedStr::from(s))
+  }
+  fn shared_str_get(s: &str, a: usize, b: usize) -> Option<usize> {
+    SharedStr::from(s).get(a..b).map(|s| s.len())
+  }
+  fn shared_str_floor(s: &str, i: usize) -> usize {
+    SharedStr::from(s).floor_char_boundary(i)
+  }
+  fn unique_str_round_trip(s: &mut str) -> &mut str {
+    <&mut str>::from(UniqueStr::from(s))
+  }
+  fn stable_vec_round_trip(v: Vec<u32>) -> Vec<u32> {
+    Vec::from(StableVec::from(v))
+  }
+  fn stable_vec_split(v: Vec<u32>, at: usize) -> Option<usize> {
+    StableVec::from(v).try_split_off(at).map(|t| Vec::from(t).len())
+  }
+  fn stable_string_round_trip(s: String) -> String {
+    String::from(StableString::from(s))
+  }
+  fn slice_iter_len(s: &[u32]) -> usize {
+    StableSliceIter::from(s).len()
+  }
+  fn matrix_get(s: &[u32], r: usize, c: usize) -> Option<u32> {
+    let m = SharedMatrix::new(s, 2, 2, 2, MatrixOrder::RowMajor)?;
+    let row = m.row_slice(r).map(|s| s.len()).unwrap_or(0);
+    let col = m.col(c).map(|c| c.count()).unwrap_or(0);
+    m.get(r, c).map(|&x| x + row as u32 + col as u32)
+  }
+  fn audio_channel(s: &[f32], ch: usize) -> usize {
+    SharedAudioBuffer::new(SharedSlice::from(s), 2, ChannelLayout::Planar)
+      .and_then(|a| a.channel(ch))
+      .map(|c| c.count())
+      .unwrap_or(0)
+  }
+  fn status_round_trip(code: i32) -> Result<(), StableStatus> {
+    StableStatus(code).to_result()
+  }
+}
+
+#[test]
+fn test_no_panic_paths() {
+  let mut data = [1_u32, 2, 3, 4];
+  assert_eq!(shared_slice_round_trip(&data).len(), 4);
+  assert_eq!(shared_slice_windows(&data, 2), 3);
+  assert_eq!(shared_slice_windows(&data, 0), 0);
+  assert!(unique_slice_copy(&mut [0; 4], &data));
+  assert!(!unique_slice_copy(&mut [0; 3], &data));
+  assert_eq!(unique_slice_round_trip(&mut data).len(), 4);
+  assert_eq!(shared_str_round_trip("hi"), "hi");
+  assert_eq!(shared_str_get("héllo", 2, 4), None);
+  assert_eq!(shared_str_floor("héllo", 2), 1);
+  assert_eq!(unique_str_round_trip(&mut String::from("hi")), "hi");
+  assert_eq!(stable_vec_round_trip(vec![1, 2]), vec![1, 2]);
+  assert_eq!(stable_vec_split(vec![1, 2], 1), Some(1));
+  assert_eq!(stable_vec_split(vec![1, 2], 3), None);
+  assert_eq!(stable_string_round_trip(String::from("hi")), "hi");
+  assert_eq!(slice_iter_len(&data), 4);
+  assert_eq!(matrix_get(&data, 1, 1), Some(4 + 2 + 2));
+  assert_eq!(audio_channel(&[0.0; 6], 1), 3);
+  assert_eq!(audio_channel(&[0.0; 6], 2), 0);
+  assert_eq!(status_round_trip(0), Ok(()));
+}
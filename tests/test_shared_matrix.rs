@@ -0,0 +1,28 @@
+#![allow(bad_style)]
+
+use chromium::*;
+
+#[test]
+fn test_SharedMatrix_row_major() {
+  // 2x3 matrix stored with a leading dimension of 4.
+  let data = [1, 2, 3, 0, 4, 5, 6];
+  let m = SharedMatrix::new(&data, 2, 3, 4, MatrixOrder::RowMajor).unwrap();
+  assert_eq!(m.get(1, 2), Some(&6));
+  assert_eq!(m.get(2, 0), None);
+  assert_eq!(&m.row_slice(1).unwrap()[..], &[4, 5, 6]);
+  assert!(m.col_slice(0).is_none());
+  assert_eq!(m.col(1).unwrap().copied().collect::<Vec<_>>(), vec![2, 5]);
+  assert_eq!(format!("{:?}", m), "[[1, 2, 3], [4, 5, 6]]");
+}
+
+#[test]
+fn test_SharedMatrix_checks() {
+  let data = [0; 5];
+  assert!(SharedMatrix::new(&data, 2, 3, 2, MatrixOrder::RowMajor).is_none());
+  assert!(SharedMatrix::new(&data, 2, 3, 3, MatrixOrder::RowMajor).is_none());
+  assert!(SharedMatrix::new(&data, 3, 2, 2, MatrixOrder::ColMajor).is_none());
+  let empty = SharedMatrix::new(&data[..0], 2, 0, 0, MatrixOrder::RowMajor);
+  let empty = empty.unwrap();
+  assert_eq!(empty.row_slice(1).unwrap().len(), 0);
+  assert_eq!(empty.row(1).unwrap().count(), 0);
+}
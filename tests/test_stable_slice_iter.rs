@@ -0,0 +1,26 @@
+#![allow(bad_style)]
+
+use chromium::*;
+
+#[test]
+fn test_StableSliceIter_both_ends() {
+  let data = [1, 2, 3, 4];
+  let mut it = StableSliceIter::from(SharedSlice::from(&data[..]));
+  assert_eq!(it.len(), 4);
+  assert_eq!(it.next(), Some(&1));
+  assert_eq!(it.next_back(), Some(&4));
+  let resumed = it.clone();
+  assert_eq!(it.copied().collect::<Vec<_>>(), vec![2, 3]);
+  assert_eq!(resumed.len(), 2);
+}
+
+#[test]
+fn test_StableSliceIter_zst() {
+  let data = [(); 3];
+  let mut it = StableSliceIter::from(&data[..]);
+  assert_eq!(it.len(), 3);
+  assert_eq!(it.next(), Some(&()));
+  assert_eq!(it.next_back(), Some(&()));
+  assert_eq!(it.as_slice().len(), 1);
+  assert_eq!(it.count(), 1);
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "unsafe_alloc")]
+#![allow(bad_style)]
+
+use chromium::*;
+
+#[test]
+fn test_StableVec_split_off_and_retain() {
+  let mut sv = StableVec::from(vec![1, 2, 3, 4, 5, 6]);
+  let mut tail = sv.split_off(3);
+  tail.retain(|x| x % 2 == 0);
+  assert_eq!(Vec::from(sv), vec![1, 2, 3]);
+  assert_eq!(Vec::from(tail), vec![4, 6]);
+}
+
+#[test]
+fn test_StableVec_retain_panic_leaves_empty() {
+  let mut sv = StableVec::from(vec![1, 2, 3]);
+  let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+    sv.retain(|_| panic!("oops"));
+  }));
+  assert!(result.is_err());
+  assert_eq!(sv.len(), 0);
+}
+
+#[test]
+fn test_StableVec_zeroed_and_from_elem() {
+  let sv: StableVec<u64> = unsafe { StableVec::zeroed(1000) }.unwrap();
+  assert!(sv.iter().all(|&x| x == 0));
+  assert_eq!(Vec::from(sv).capacity(), 1000);
+  let zst: StableVec<()> = unsafe { StableVec::zeroed(5) }.unwrap();
+  assert_eq!(zst.len(), 5);
+  let empty: StableVec<u8> = unsafe { StableVec::zeroed(0) }.unwrap();
+  assert_eq!(empty.len(), 0);
+  let huge = unsafe { StableVec::<u64>::zeroed(usize::MAX / 4) };
+  assert!(huge.is_none());
+  let sv = StableVec::from_elem(-1_i16, 10);
+  assert_eq!(Vec::from(sv), vec![-1; 10]);
+}
+
+#[test]
+#[cfg(all(feature = "poison_on_release", not(feature = "no_panic")))]
+#[should_panic]
+fn test_StableVec_poisoned_after_take_vec() {
+  let mut sv = StableVec::from(vec![1, 2, 3]);
+  let _ = sv.take_vec();
+  let _ = sv.take_vec();
+}
+
+#[test]
+#[cfg(all(feature = "poison_on_release", feature = "no_panic"))]
+fn test_StableVec_poisoned_is_empty_with_no_panic() {
+  let mut sv = StableVec::from(vec![1, 2, 3]);
+  assert_eq!(sv.take_vec(), vec![1, 2, 3]);
+  assert!(sv.take_vec().is_empty());
+  let mut ss = StableString::from(String::from("hi"));
+  assert_eq!(ss.take_string(), "hi");
+  assert!(ss.take_string().is_empty());
+}
+
+#[test]
+fn test_StableVec_freeze_raw_round_trip() {
+  let frozen = StableVec::from(vec![1_u8, 2, 3]).freeze();
+  let raw = frozen.clone().into_raw();
+  assert_eq!(frozen.ref_count(), 2);
+  let back = unsafe { StableArcSlice::from_raw(raw) };
+  drop(frozen);
+  assert_eq!(back.ref_count(), 1);
+  assert_eq!(&back[..], &[1, 2, 3]);
+}
@@ -60,3 +60,74 @@ fn test_StableString_to_from() {
   let back_as_a_string: String = stable_string.into();
   assert_eq!(back_as_a_string, String::from("hello"));
 }
+
+#[test]
+#[cfg(feature = "std")]
+fn test_StableStatus_to_from_ErrorKind() {
+  use std::io::ErrorKind;
+  for kind in [ErrorKind::NotFound, ErrorKind::TimedOut, ErrorKind::Other] {
+    let status = StableStatus::from(kind);
+    assert!(status.is_err());
+    assert_eq!(ErrorKind::from(status), kind);
+  }
+  assert_eq!(ErrorKind::from(StableStatus(5)), ErrorKind::Other);
+}
+
+#[test]
+fn test_SharedSlice_of_extern_C_fn_to_from() {
+  extern "C" fn double(x: u32) -> u32 {
+    x * 2
+  }
+  let table: [Option<extern "C" fn(u32) -> u32>; 2] = [Some(double), None];
+  let shared = SharedSlice::from(&table[..]);
+  let back: &[Option<extern "C" fn(u32) -> u32>] = shared.into();
+  assert_eq!(back[0].map(|f| f(21)), Some(42));
+  assert!(back[1].is_none());
+}
+
+#[test]
+fn test_SharedSlice_of_c_void_ptr_to_from() {
+  let mut x = 5_u32;
+  let ptrs =
+    [&mut x as *mut u32 as *mut core::ffi::c_void, core::ptr::null_mut()];
+  let shared = SharedSlice::from(&ptrs[..]);
+  let back: &[*mut core::ffi::c_void] = shared.into();
+  assert_eq!(back, &ptrs[..]);
+}
+
+#[test]
+fn test_StableResult_Infallible_to_from() {
+  let r: StableResult<u32, core::convert::Infallible> =
+    StableResult::from(Ok(7));
+  assert_eq!(core::mem::size_of_val(&r), 8);
+  assert_eq!(r.into_result(), Ok(7));
+}
+
+#[test]
+fn test_Ordering_layout() {
+  use core::cmp::Ordering;
+  extern "C" fn compare(a: i32, b: i32) -> Ordering {
+    a.cmp(&b)
+  }
+  let comparators: [extern "C" fn(i32, i32) -> Ordering; 1] = [compare];
+  let shared = SharedSlice::from(&comparators[..]);
+  assert_eq!((shared[0])(1, 2), Ordering::Less);
+  let results = [Ordering::Less, Ordering::Equal, Ordering::Greater];
+  let bytes: [i8; 3] = unsafe { core::mem::transmute(results) };
+  assert_eq!(bytes, [-1, 0, 1]);
+}
+
+#[test]
+fn test_maybe_uninit_write_out() {
+  use core::mem::MaybeUninit;
+  let mut storage = [MaybeUninit::<u32>::uninit(); 4];
+  let mut out: UniqueSlice<'_, MaybeUninit<u32>> =
+    UniqueSlice::from(&mut storage[..]);
+  assert_eq!(out.len(), 4);
+  for (i, slot) in out.iter_mut().enumerate() {
+    slot.write(i as u32 * 10);
+  }
+  let written: Vec<u32> =
+    storage.iter().map(|m| unsafe { m.assume_init() }).collect();
+  assert_eq!(written, [0, 10, 20, 30]);
+}